@@ -0,0 +1,350 @@
+use crate::{
+    constants::{self, C_M_PER_S},
+    integrate::integrate,
+    units::length::{KILOMETER_TO_METER, MPC_TO_KILOMETERS},
+    units::{H2MsunPerMpc3, LittleHScaled, PositiveFloat},
+    DimensionlessFloat, DimensionlessPositiveFloat, FloatingPointUnit, Gyr, Kelvin,
+    KilogramPerMeter3, KmPerSecPerMpc, Meter, Mpc, MsunPerMpc3, Redshift, Seconds,
+};
+
+/// The homogeneous expansion history of a cosmological model.
+///
+/// Implementors provide the dimensionless expansion rate $E(z) = H(z) / H_0$
+/// and a few parameters at `z=0`. Everything derived from them, including
+/// [`Distances`](crate::Distances) and
+/// [`LittleHDistances`](crate::LittleHDistances), is provided.
+///
+/// # Examples
+///
+/// A custom model only needs to describe its expansion rate:
+///
+/// ```
+/// use cosmocalc::{
+///     Background, DimensionlessFloat, DimensionlessPositiveFloat, Distances,
+///     FloatingPointUnit, Kelvin, KmPerSecPerMpc, Redshift,
+/// };
+///
+/// /// Einstein-de Sitter universe.
+/// struct EinsteinDeSitter;
+///
+/// impl Background for EinsteinDeSitter {
+///     fn E(&self, z: Redshift) -> DimensionlessPositiveFloat {
+///         DimensionlessPositiveFloat::new((1. + z.0).powf(1.5)).unwrap()
+///     }
+///     fn H_0(&self) -> KmPerSecPerMpc {
+///         KmPerSecPerMpc::new(70.)
+///     }
+///     fn omega_k0(&self) -> DimensionlessFloat {
+///         DimensionlessFloat::zero()
+///     }
+///     fn T_CMB0(&self) -> Option<Kelvin> {
+///         None
+///     }
+/// }
+///
+/// let d_c = EinsteinDeSitter.radial_comoving_distance(Redshift::new(1.));
+/// ```
+pub trait Background {
+    /// Dimensionless expansion rate $E(z) = H(z) / H_0$.
+    fn E(&self, z: Redshift) -> DimensionlessPositiveFloat;
+    /// Hubble constant at `z=0`.
+    fn H_0(&self) -> KmPerSecPerMpc;
+    /// Dimensionless effective curvature density (density/critical density) at `z=0`
+    fn omega_k0(&self) -> DimensionlessFloat;
+    /// Temperature of the CMB at `z=0`, if the model has one.
+    fn T_CMB0(&self) -> Option<Kelvin>;
+
+    /// Hubble expansion rate (km/s/Mpc) at redshift z.
+    fn H(&self, z: Redshift) -> KmPerSecPerMpc {
+        KmPerSecPerMpc::new(self.H_0().0 * self.E(z).0)
+    }
+
+    /// Scale factor at redshift z.
+    fn scale_factor(&self, z: Redshift) -> DimensionlessPositiveFloat {
+        PositiveFloat(1.0 / (z.0 + 1.0))
+    }
+
+    /// Dimensionless hubble parameter h where 100 km/s/Mpc * h = H0
+    fn little_h(&self) -> DimensionlessPositiveFloat {
+        PositiveFloat(self.H_0().0 / 100.0)
+    }
+
+    /// Hubble time: Inverse of the Hubble constant H_0
+    fn hubble_time(&self) -> Seconds {
+        // H_0 units are km/s/Mpc so we need to convert Mpc to km
+        // such that the distance units cancel
+        Seconds::new(1. / self.H_0().0 * MPC_TO_KILOMETERS)
+    }
+
+    /// Hubble distance in Mpc: $D_H = c / H_0$.
+    fn hubble_distance(&self) -> Mpc {
+        // Factor of 1000 to convert c in m/s to c in km/s so that
+        // the units cancel.
+        Mpc::new(C_M_PER_S / (self.H_0().0 * KILOMETER_TO_METER))
+    }
+
+    /// Remove the factor of h from a quantity using this cosmology's h.
+    fn remove_little_h<T: LittleHScaled>(&self, quantity: T) -> T::Unit {
+        quantity.to_unit(self.little_h())
+    }
+
+    /// Express a quantity in h-scaled units using this cosmology's h.
+    fn to_little_h<T: LittleHScaled>(&self, quantity: T::Unit) -> T {
+        T::from_unit(quantity, self.little_h())
+    }
+
+    /// Critical mass density at redshift z.
+    fn critical_density(&self, z: Redshift) -> KilogramPerMeter3 {
+        KilogramPerMeter3::new(
+            3. * self.H(z).powi(2)
+                / (8. * constants::PI * constants::G * MPC_TO_KILOMETERS.powi(2)),
+        )
+    }
+
+    /// Critical mass density at redshift z in h^2 Msun/Mpc^3.
+    fn critical_density_little_h(&self, z: Redshift) -> H2MsunPerMpc3 {
+        self.to_little_h(MsunPerMpc3::from(self.critical_density(z)))
+    }
+
+    /// Dimensionless effective curvature density (density/critical density) at `z>0`
+    fn omega_k(&self, z: Redshift) -> DimensionlessFloat {
+        DimensionlessFloat(self.omega_k0().0 * (1.0 + z.0).powi(2) / self.E(z).0.powi(2))
+    }
+
+    /// Lookback time
+    ///
+    /// The difference in ages of the universe from now to when the light
+    /// was emitted from the object at `z`.
+    fn lookback_time(&self, z: Redshift) -> Gyr {
+        let integral = integrate(
+            |z_prime| 1. / ((1. + z_prime) * self.E(Redshift::new(z_prime)).0),
+            0.,
+            z.0,
+        );
+        Seconds::new(self.hubble_time().0 * integral).into()
+    }
+
+    /// Age of the universe at redshift z: the time since the Big Bang.
+    ///
+    /// Integrated in the scale factor from $a = 0$,
+    /// $t = t_H \int_0^{a} \frac{da'}{a' E(a')}$, so the radiation era is
+    /// included for models with a CMB temperature.
+    fn age(&self, z: Redshift) -> Gyr {
+        let a = self.scale_factor(z).0;
+        Seconds::new(self.hubble_time().0 * integrate(|a| time_integrand(self, a), 0., a)).into()
+    }
+
+    /// Age of the universe today.
+    fn age0(&self) -> Gyr {
+        self.age(Redshift::zero())
+    }
+
+    /// Cosmic time elapsed between redshifts `z_1` and `z_2`.
+    ///
+    /// This is positive when `z_2` is lower (later) than `z_1`.
+    fn time_between(&self, z_1: Redshift, z_2: Redshift) -> Gyr {
+        let a_1 = self.scale_factor(z_1).0;
+        let a_2 = self.scale_factor(z_2).0;
+        Seconds::new(self.hubble_time().0 * integrate(|a| time_integrand(self, a), a_1, a_2)).into()
+    }
+
+    /// Time taken by light emitted at `z_2` to reach an observer at `z_1`.
+    ///
+    /// This is positive when the source at `z_2` is more distant than `z_1`.
+    fn light_travel_time(&self, z_1: Redshift, z_2: Redshift) -> Gyr {
+        self.time_between(z_2, z_1)
+    }
+
+    /// Conformal time at redshift z, $\eta = \int_0^t dt' / a(t')$.
+    fn conformal_time(&self, z: Redshift) -> Gyr {
+        Seconds::new(self.hubble_time().0 * conformal_time_integral(self, z)).into()
+    }
+
+    /// Comoving particle horizon at redshift z, $c \eta(z)$: the comoving
+    /// distance light could have travelled since the Big Bang.
+    fn particle_horizon(&self, z: Redshift) -> Mpc {
+        Mpc::new(self.hubble_distance().0 * conformal_time_integral(self, z))
+    }
+
+    /// Comoving event horizon at redshift z: the largest comoving distance
+    /// from which light emitted at z can ever reach us.
+    ///
+    /// This is `None` if the universe does not accelerate forever, i.e. if
+    /// $a^2 E(a)$ grows no faster than $a$ in the far future (as without dark
+    /// energy) or the expansion stops.
+    fn event_horizon(&self, z: Redshift) -> Option<Mpc> {
+        // Find how a^2 E(a) scales in the far future
+        let growth = |a: f64| a.powi(2) * self.E(Redshift::new(1. / a - 1.)).0;
+        let exponent =
+            (growth(FAR_FUTURE_SCALE_FACTOR) / growth(FAR_FUTURE_SCALE_FACTOR / 10.)).log10();
+        if exponent.is_nan() || exponent <= 1. + EVENT_HORIZON_EXPONENT_MARGIN {
+            return None;
+        }
+
+        // With u = 1/a = 1+z the integral from a(z) to infinity runs over
+        // all future redshifts
+        let integral = integrate(|z| 1. / self.E(Redshift::new(z)).0, -1., z.0);
+        Some(Mpc::new(self.hubble_distance().0 * integral))
+    }
+
+    /// Proper event horizon at redshift z.
+    ///
+    /// See [`Background::event_horizon`].
+    fn proper_event_horizon(&self, z: Redshift) -> Option<Mpc> {
+        self.event_horizon(z)
+            .map(|horizon| Mpc::new(horizon.0 * self.scale_factor(z).0))
+    }
+
+    /// Hubble radius (proper radius of the Hubble sphere) at redshift z, $c / H(z)$.
+    fn hubble_radius(&self, z: Redshift) -> Mpc {
+        Mpc::new(self.hubble_distance().0 / self.E(z).0)
+    }
+
+    /// Lookback distance
+    ///
+    /// Proper distance between now and redshift z
+    fn lookback_distance(&self, z: Redshift) -> Mpc {
+        let lookback_time_seconds: Seconds = self.lookback_time(z).into();
+        Meter::new(lookback_time_seconds.0 * C_M_PER_S).into()
+    }
+}
+
+/// Scale factor at which the far future expansion is probed to decide
+/// whether an event horizon exists.
+const FAR_FUTURE_SCALE_FACTOR: f64 = 1e8;
+
+/// How much faster than $a$ the quantity $a^2 E(a)$ must grow for the
+/// event horizon to be considered finite.
+const EVENT_HORIZON_EXPONENT_MARGIN: f64 = 1e-3;
+
+/// $\int_0^{a(z)} da / (a^2 E(a))$, conformal time in units of the Hubble time.
+///
+/// The integrand diverges as $a^{-1/2}$ during matter domination, so the
+/// integral is taken over $s = \sqrt{a}$, in which it is smooth.
+fn conformal_time_integral<B: Background + ?Sized>(background: &B, z: Redshift) -> f64 {
+    let s_max = background.scale_factor(z).0.sqrt();
+    integrate(
+        |s| 2. / (s.powi(3) * background.E(Redshift::new(s.powi(-2) - 1.)).0),
+        0.,
+        s_max,
+    )
+}
+
+/// $1 / (a E(a))$, the integrand of cosmic time in the scale factor.
+fn time_integrand<B: Background + ?Sized>(background: &B, a: f64) -> f64 {
+    1. / (a * background.E(Redshift::new(1. / a - 1.)).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Distances, FLRWCosmology};
+
+    /// A flat universe of matter and radiation only.
+    struct MatterRadiation {
+        omega_m0: f64,
+        omega_r0: f64,
+    }
+
+    impl Background for MatterRadiation {
+        fn E(&self, z: Redshift) -> DimensionlessPositiveFloat {
+            PositiveFloat(
+                (self.omega_m0 * (1. + z.0).powi(3) + self.omega_r0 * (1. + z.0).powi(4)).sqrt(),
+            )
+        }
+        fn H_0(&self) -> KmPerSecPerMpc {
+            KmPerSecPerMpc::new(70.)
+        }
+        fn omega_k0(&self) -> DimensionlessFloat {
+            DimensionlessFloat::zero()
+        }
+        fn T_CMB0(&self) -> Option<Kelvin> {
+            None
+        }
+    }
+
+    #[test]
+    fn age_of_flat_lambda_cdm() {
+        // t_0 = 2 / (3 H_0 sqrt(Omega_L)) asinh(sqrt(Omega_L / Omega_M))
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let t_H: Gyr = cosmology.hubble_time().into();
+        let expected = 2. / (3. * 0.7_f64.sqrt()) * (0.7_f64 / 0.3).sqrt().asinh() * t_H.0;
+        assert!((cosmology.age0().0 / expected - 1.).abs() < 1e-9);
+
+        let z = Redshift::new(2.);
+        let lookback = cosmology.lookback_time(z);
+        assert!((cosmology.age(z).0 + lookback.0 - expected).abs() < 1e-8);
+        assert!((cosmology.time_between(z, Redshift::zero()).0 - lookback.0).abs() < 1e-8);
+        assert!((cosmology.time_between(Redshift::zero(), z).0 + lookback.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn light_travel_time_between_redshifts() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let z_1 = Redshift::new(0.5);
+        let z_2 = Redshift::new(2.);
+
+        let expected = cosmology.lookback_time(z_2).0 - cosmology.lookback_time(z_1).0;
+        assert!((cosmology.light_travel_time(z_1, z_2).0 / expected - 1.).abs() < 1e-9);
+        assert!((cosmology.light_travel_time(z_2, z_1).0 / expected + 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn horizons_in_einstein_de_sitter() {
+        let cosmology = FLRWCosmology::two_component(1., 0., 70.);
+        let d_H = cosmology.hubble_distance().0;
+
+        // eta = 2 / (H_0 sqrt(1+z)), so the particle horizon today is 2 D_H
+        let z = Redshift::new(3.);
+        assert!((cosmology.particle_horizon(Redshift::zero()).0 / (2. * d_H) - 1.).abs() < 1e-9);
+        assert!((cosmology.particle_horizon(z).0 / d_H - 1.).abs() < 1e-9);
+        let t_H: Gyr = cosmology.hubble_time().into();
+        assert!((cosmology.conformal_time(z).0 / t_H.0 - 1.).abs() < 1e-9);
+
+        // Light from anywhere eventually reaches us
+        assert_eq!(cosmology.event_horizon(Redshift::zero()), None);
+        assert!((cosmology.hubble_radius(z).0 - d_H / 8.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn horizons_with_dark_energy() {
+        // de Sitter: the proper event horizon is the Hubble radius
+        let de_sitter = FLRWCosmology::two_component(0., 1., 70.);
+        let d_H = de_sitter.hubble_distance().0;
+        let z = Redshift::new(1.);
+        assert!((de_sitter.event_horizon(z).unwrap().0 / (2. * d_H) - 1.).abs() < 1e-9);
+        assert!((de_sitter.proper_event_horizon(z).unwrap().0 / d_H - 1.).abs() < 1e-9);
+
+        // The event horizon shrinks to zero in comoving units and is always
+        // beyond the comoving distance of objects we can see today
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let horizon = cosmology.event_horizon(Redshift::zero()).unwrap();
+        let d_c = cosmology.radial_comoving_distance(Redshift::new(1.));
+        assert!(horizon.0 > 3000. && horizon.0 < 5000.);
+        assert!(cosmology.event_horizon(Redshift::new(1.)).unwrap().0 > d_c.0 + horizon.0 - 1e-6);
+        assert!(cosmology.particle_horizon(Redshift::zero()).0 > 3. * d_H);
+    }
+
+    #[test]
+    fn age_in_radiation_era() {
+        // t(a) = 4 Omega_r^{3/2} / (3 H_0 Omega_m^2) [1 - (1 - x/2) sqrt(1+x)]
+        // with x = a Omega_m / Omega_r
+        let cosmology = MatterRadiation {
+            omega_m0: 1. - 9e-5,
+            omega_r0: 9e-5,
+        };
+        let t_H: Gyr = cosmology.hubble_time().into();
+        let analytic = |z: f64| {
+            let x = cosmology.omega_m0 / (cosmology.omega_r0 * (1. + z));
+            let t = 4. * cosmology.omega_r0.powf(1.5) / (3. * cosmology.omega_m0.powi(2))
+                * (1. - (1. - x / 2.) * (1. + x).sqrt());
+            t * t_H.0
+        };
+
+        // Today, recombination and nucleosynthesis
+        for z in [0., 1100., 1e9] {
+            let age = cosmology.age(Redshift::new(z)).0;
+            assert!((age / analytic(z) - 1.).abs() < 1e-6, "z = {}", z);
+        }
+    }
+}
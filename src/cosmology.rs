@@ -1,16 +1,20 @@
 use anyhow::anyhow;
 
 mod omega_factors;
+mod tabulated;
 
 pub use omega_factors::OmegaFactors;
+pub(crate) use tabulated::read_columns;
+pub use tabulated::TabulatedCosmology;
 
 use crate::{
     constants::{self, C_M_PER_S, DEFAULT_NEUTRINO_MASSES, DEFAULT_N_EFF},
+    dark_energy::CPLParameters,
     eV,
-    units::length::{KILOMETER_TO_METER, MPC_TO_KILOMETERS},
-    units::{HInvMpc, PositiveFloat},
-    DimensionlessFloat, DimensionlessPositiveFloat, FloatingPointUnit, Gyr, Kelvin,
-    KilogramsPerMeter3, KmPerSecPerMpc, Meter, Mpc, Redshift, Seconds,
+    units::length::MPC_TO_KILOMETERS,
+    units::{H2MsunPerMpc3, HInvMpc, PositiveFloat},
+    Background, DimensionlessFloat, DimensionlessPositiveFloat, FloatingPointUnit, Kelvin,
+    KilogramPerMeter3, KmPerSecPerMpc, MsunPerMpc3, Redshift,
 };
 
 /// Represents an FLRW cosmology.
@@ -38,7 +42,7 @@ use crate::{
 /// A more complex cosmology specifying the CMB temperature and no neutrinos:
 ///
 /// ```
-/// use cosmocalc::{Distances, cosmology::OmegaFactors, Redshift, Mpc, FLRWCosmology, FloatingPointUnit, units::PositiveFloat};
+/// use cosmocalc::{Background, Distances, cosmology::OmegaFactors, Redshift, Mpc, FLRWCosmology, FloatingPointUnit, units::PositiveFloat};
 /// let omega_m = 0.299;
 /// let omega_de = 0.7;
 /// let omega_baryon = 0.05;
@@ -55,9 +59,9 @@ use crate::{
 ///     Some(vec![]),
 /// )
 /// .unwrap();
-
+///
 /// let z = Redshift::new(2.0);
-
+///
 /// let t = cosmology.lookback_time(z);
 /// let omega_at_z = cosmology.omega_tot(z);
 /// let omega_de_at_z = cosmology.omega_de(z);
@@ -69,6 +73,18 @@ use crate::{
 /// let expansion_rate_at_z = cosmology.H(z);
 /// let a_z = cosmology.scale_factor(z);
 /// ```
+///
+/// # Serialization
+///
+/// With the `serde` feature enabled, only the defining parameters are
+/// serialized. Derived densities (`omega_k0`, `omega_gamma0`, `omega_nu0`,
+/// `omega_tot0`) are recomputed when a cosmology is deserialized.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerializedFLRWCosmology", into = "SerializedFLRWCosmology")
+)]
 pub struct FLRWCosmology {
     /// A descriptive name.
     pub name: Option<String>,
@@ -95,6 +111,9 @@ pub struct FLRWCosmology {
     pub N_eff: DimensionlessPositiveFloat,
     /// Mass of neutrino species in eV.
     pub m_nu: Vec<eV>,
+
+    /// Dark energy equation of state (a cosmological constant by default).
+    pub dark_energy: CPLParameters,
 }
 
 impl FLRWCosmology {
@@ -153,7 +172,7 @@ impl FLRWCosmology {
         Ok(Self {
             name,
             reference,
-            H_0,
+            H_0: KmPerSecPerMpc::new(H_0),
             omega,
             omega_k0,
             omega_gamma0,
@@ -162,51 +181,20 @@ impl FLRWCosmology {
             T_CMB0: T_CMB0.map(Kelvin),
             N_eff,
             m_nu,
+            dark_energy: CPLParameters::default(),
         })
     }
 
-    pub fn E(&self, z: Redshift) -> Mpc {
-        Mpc::new(
-            (self.omega.Omega_M0.0 * (1. + z.0).powi(3)
-                + self.omega_k0.0 * (1. + z.0).powi(2)
-                + self.omega.Omega_DE0.0
-                + (self.omega_gamma0.0 + self.omega_nu0.0) * (1. + z.0).powi(4))
-            .sqrt(),
-        )
-    }
-
-    /// Hubble expansion rate (km/s/Mpc) at redshift z.
-    pub fn H(&self, z: Redshift) -> KmPerSecPerMpc {
-        self.H_0 * self.E(z).0
-    }
-
-    /// Scale factor at redshift z.
-    pub fn scale_factor(&self, z: Redshift) -> DimensionlessPositiveFloat {
-        PositiveFloat(1.0 / (z.0 + 1.0))
-    }
-
-    /// Dimensionless hubble parameter h where 100 km/s/Mpc * h = H0
-    pub fn little_h(&self) -> DimensionlessPositiveFloat {
-        DimensionlessPositiveFloat::new(self.H_0 / 100.0).unwrap()
-    }
-
-    /// Hubble time: Inverse of the Hubble constant H_0
-    pub fn hubble_time(&self) -> Seconds {
-        // H_0 units are km/s/Mpc so we need to convert Mpc to km
-        // such that the distance units cancel
-        Seconds::new(1. / self.H_0 * MPC_TO_KILOMETERS)
-    }
-
-    /// Hubble distance in Mpc: $D_H = c / H_0$.
-    pub fn hubble_distance(&self) -> Mpc {
-        // Factor of 1000 to convert c in m/s to c in km/s so that
-        // the units cancel.
-        Mpc::new(C_M_PER_S / (self.H_0 * KILOMETER_TO_METER))
+    /// Use a dark energy equation of state $w(z) = w_0 + w_a z / (1 + z)$
+    /// in place of a cosmological constant.
+    pub fn with_dark_energy(mut self, w_0: f64, w_a: f64) -> Self {
+        self.dark_energy = CPLParameters::new(w_0, w_a);
+        self
     }
 
     /// Hubble distance in h^{-1} Mpc.
     pub fn hubble_distance_little_h(&self) -> HInvMpc {
-        C_M_PER_S / (1.0e5)
+        HInvMpc::new(C_M_PER_S / (1.0e5))
     }
 
     /// CMB temperature at redshift z.
@@ -221,7 +209,7 @@ impl FLRWCosmology {
     /// Neutrino temperature at redshift z.
     pub fn T_nu(&self, z: Redshift) -> Kelvin {
         let T_nu = match self.T_CMB0 {
-            Some(T_cmb) => Kelvin(T_cmb.0 * (*constants::T_NU_TO_T_GAMMA_RATIO).0),
+            Some(T_cmb) => Kelvin(T_cmb.0 * constants::T_NU_TO_T_GAMMA_RATIO.0),
             None => Kelvin::zero(),
         };
 
@@ -232,19 +220,16 @@ impl FLRWCosmology {
         }
     }
 
-    /// Critical mass density at redshift z.
-    pub fn critical_density(&self, z: Redshift) -> KilogramsPerMeter3 {
-        if z == Redshift::zero() {
-            PositiveFloat(
-                3. * self.H_0.powi(2)
-                    / (8. * constants::PI * constants::G * MPC_TO_KILOMETERS.powi(2)),
-            )
-        } else {
-            PositiveFloat(
-                3. * self.H(z).powi(2)
-                    / (8. * constants::PI * constants::G * MPC_TO_KILOMETERS.powi(2)),
-            )
-        }
+    /// Mean (non-relativistic) matter density at redshift z.
+    pub fn mean_matter_density(&self, z: Redshift) -> KilogramPerMeter3 {
+        KilogramPerMeter3::new(
+            self.omega_m0().0 * self.critical_density(Redshift::zero()).0 * (1.0 + z.0).powi(3),
+        )
+    }
+
+    /// Mean (non-relativistic) matter density at redshift z in h^2 Msun/Mpc^3.
+    pub fn mean_matter_density_little_h(&self, z: Redshift) -> H2MsunPerMpc3 {
+        self.to_little_h(MsunPerMpc3::from(self.mean_matter_density(z)))
     }
 
     /// Dimensionless photon density (density/critical density) at `z=0`.
@@ -279,16 +264,6 @@ impl FLRWCosmology {
         DimensionlessFloat(self.omega_dm0().0 * (1.0 + z.0).powi(3) * 1. / self.E(z).0.powi(2))
     }
 
-    /// Dimensionless effective curvature density (density/critical density) at `z=0`
-    pub fn omega_k0(&self) -> DimensionlessFloat {
-        self.omega_k0
-    }
-
-    /// Dimensionless effective curvature density (density/critical density) at `z>0`
-    pub fn omega_k(&self, z: Redshift) -> DimensionlessFloat {
-        DimensionlessFloat(self.omega_k0().0 * (1.0 + z.0).powi(2) * 1. / self.E(z).0.powi(2))
-    }
-
     /// Dimensionless matter density (density/critical density) at `z=0`
     pub fn omega_m0(&self) -> DimensionlessFloat {
         self.omega.Omega_M0
@@ -316,7 +291,9 @@ impl FLRWCosmology {
 
     /// Dimensionless dark energy density (density/critical density) at `z>0`.
     pub fn omega_de(&self, z: Redshift) -> DimensionlessFloat {
-        DimensionlessFloat(self.omega_de0().0 / self.E(z).0.powi(2))
+        DimensionlessFloat(
+            self.omega_de0().0 * self.dark_energy.density_scaling(z) / self.E(z).0.powi(2),
+        )
     }
 
     /// Dimensionless total density (density/critical density) at `z=0`.
@@ -338,27 +315,77 @@ impl FLRWCosmology {
         self.omega_k0() == DimensionlessFloat::zero()
             && self.omega_tot0() == DimensionlessFloat::one()
     }
+}
 
-    /// Lookback time
-    ///
-    /// The difference in ages of the universe from now to when the light
-    /// was emitted from the object at `z`.
-    pub fn lookback_time(&self, z: Redshift) -> Gyr {
-        let mut integrand: f64 = 0.0;
-        let mut z_prime = 0.0;
-        let DZ = 0.0001;
-        while z_prime < z.0 {
-            z_prime += DZ / 2.;
-            integrand += (DZ / 2.) / ((1. + z_prime) * self.E(Redshift::new(z_prime)).0);
-        }
-        Seconds::new(self.hubble_time().0 * integrand).into()
+impl Background for FLRWCosmology {
+    fn E(&self, z: Redshift) -> DimensionlessPositiveFloat {
+        PositiveFloat(
+            (self.omega.Omega_M0.0 * (1. + z.0).powi(3)
+                + self.omega_k0.0 * (1. + z.0).powi(2)
+                + self.omega.Omega_DE0.0 * self.dark_energy.density_scaling(z)
+                + (self.omega_gamma0.0 + self.omega_nu0.0) * (1. + z.0).powi(4))
+            .sqrt(),
+        )
     }
 
-    /// Lookback distance
-    ///
-    /// Proper distance between now and redshift z
-    pub fn lookback_distance(&self, z: Redshift) -> Mpc {
-        let lookback_time_seconds: Seconds = self.lookback_time(z).into();
-        Meter::new(lookback_time_seconds.0 * constants::C_M_PER_S).into()
+    fn H_0(&self) -> KmPerSecPerMpc {
+        self.H_0
+    }
+
+    fn omega_k0(&self) -> DimensionlessFloat {
+        self.omega_k0
+    }
+
+    fn T_CMB0(&self) -> Option<Kelvin> {
+        self.T_CMB0
+    }
+}
+
+/// The defining parameters of an [`FLRWCosmology`] as they are serialized.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SerializedFLRWCosmology {
+    name: Option<String>,
+    reference: Option<String>,
+    H_0: KmPerSecPerMpc,
+    omega: OmegaFactors,
+    T_CMB0: Option<Kelvin>,
+    N_eff: DimensionlessPositiveFloat,
+    m_nu: Vec<eV>,
+    #[serde(default)]
+    dark_energy: CPLParameters,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedFLRWCosmology> for FLRWCosmology {
+    type Error = anyhow::Error;
+
+    fn try_from(cosmology: SerializedFLRWCosmology) -> Result<Self, Self::Error> {
+        FLRWCosmology::new(
+            cosmology.name,
+            cosmology.reference,
+            cosmology.H_0.0,
+            cosmology.omega,
+            cosmology.T_CMB0.map(|T| T.0),
+            Some(cosmology.N_eff),
+            Some(cosmology.m_nu),
+        )
+        .map(|c| c.with_dark_energy(cosmology.dark_energy.w_0, cosmology.dark_energy.w_a))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<FLRWCosmology> for SerializedFLRWCosmology {
+    fn from(cosmology: FLRWCosmology) -> Self {
+        Self {
+            name: cosmology.name,
+            reference: cosmology.reference,
+            H_0: cosmology.H_0,
+            omega: cosmology.omega,
+            T_CMB0: cosmology.T_CMB0,
+            N_eff: cosmology.N_eff,
+            m_nu: cosmology.m_nu,
+            dark_energy: cosmology.dark_energy,
+        }
     }
 }
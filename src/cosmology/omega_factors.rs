@@ -1,6 +1,12 @@
 use crate::{units::FloatingPointUnit, DimensionlessFloat};
 
 /// Represents a collection of dimensionless density parameters.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "SerializedOmegaFactors")
+)]
 pub struct OmegaFactors {
     /// Ratio of non-relativistic matter to critical density at `z=0`.
     pub Omega_M0: DimensionlessFloat,
@@ -37,3 +43,21 @@ impl OmegaFactors {
         DimensionlessFloat(1.0) - self.Omega_M0 - self.Omega_DE0 - omega_nu0 - omega_gamma0
     }
 }
+
+/// Unvalidated omega factors as they appear in serialized data.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedOmegaFactors {
+    Omega_M0: f64,
+    Omega_DE0: f64,
+    Omega_b0: f64,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedOmegaFactors> for OmegaFactors {
+    type Error = anyhow::Error;
+
+    fn try_from(omega: SerializedOmegaFactors) -> Result<Self, Self::Error> {
+        OmegaFactors::new(omega.Omega_M0, omega.Omega_DE0, omega.Omega_b0)
+    }
+}
@@ -0,0 +1,241 @@
+use anyhow::anyhow;
+
+use crate::{
+    interpolate::CubicSpline, units::PositiveFloat, Background, DimensionlessFloat,
+    DimensionlessPositiveFloat, FloatingPointUnit, Kelvin, KilogramPerMeter3, KmPerSecPerMpc,
+    Redshift,
+};
+
+/// A cosmology defined by a tabulated expansion history $H(z)$.
+///
+/// The table is interpolated with a natural cubic spline in
+/// $\ln H$ against $\ln (1+z)$, which is smooth for the power laws of
+/// matter and radiation domination. Beyond the tabulated redshifts $H(z)$ is
+/// extrapolated as a power law in $(1+z)$.
+///
+/// # Examples
+///
+/// ```
+/// use cosmocalc::{cosmology::TabulatedCosmology, Distances, FloatingPointUnit, Redshift};
+///
+/// let table = "
+/// ## z    H [km/s/Mpc]
+/// 0.0    70.0
+/// 0.5    91.6
+/// 1.0    123.2
+/// 2.0    208.1
+/// 3.0    314.7
+/// ";
+/// let cosmology = TabulatedCosmology::from_text(table).unwrap();
+/// let d_l = cosmology.luminosity_distance(Redshift::new(1.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabulatedCosmology {
+    /// A descriptive name.
+    pub name: Option<String>,
+
+    /// Hubble constant at `z=0` (km/(s/Mpc)).
+    pub H_0: KmPerSecPerMpc,
+    /// Omega curvature at z=0 (flat unless set with [`TabulatedCosmology::with_curvature`]).
+    pub omega_k0: DimensionlessFloat,
+    /// Omega matter at z=0, if known.
+    pub omega_m0: Option<DimensionlessFloat>,
+    /// Temperature of the CMB at `z=0`, if known.
+    pub T_CMB0: Option<Kelvin>,
+
+    /// $\ln E$ as a function of $\ln (1+z)$.
+    ln_E: CubicSpline,
+}
+
+impl TabulatedCosmology {
+    /// Instantiate a cosmology from the expansion rate `H` at redshifts `z`.
+    ///
+    /// The table must include `z=0`, which defines `H_0`.
+    pub fn new(z: &[Redshift], H: &[KmPerSecPerMpc]) -> Result<Self, anyhow::Error> {
+        if z.len() != H.len() {
+            return Err(anyhow!(
+                "redshift and H(z) columns must have the same length"
+            ));
+        }
+        let mut rows: Vec<(f64, f64)> = z.iter().zip(H).map(|(z, H)| (z.0, H.0)).collect();
+        rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let H_0 = match rows.first() {
+            Some((z, H)) if *z == 0. => *H,
+            _ => return Err(anyhow!("tabulated H(z) must include z=0")),
+        };
+        if rows.iter().any(|(_, H)| *H <= 0.) {
+            return Err(anyhow!("tabulated H(z) must be positive"));
+        }
+
+        let ln_E = CubicSpline::new(
+            rows.iter().map(|(z, _)| (1. + z).ln()).collect(),
+            rows.iter().map(|(_, H)| (H / H_0).ln()).collect(),
+        )?;
+
+        Ok(Self {
+            name: None,
+            H_0: KmPerSecPerMpc::new(H_0),
+            omega_k0: DimensionlessFloat::zero(),
+            omega_m0: None,
+            T_CMB0: None,
+            ln_E,
+        })
+    }
+
+    /// Read a whitespace separated table with redshift in the first column
+    /// and $H(z)$ in km/s/Mpc in the second.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, as are any
+    /// further columns. CAMB has no background output file, so its $H(z)$
+    /// must be tabulated this way. For the CLASS `background.dat` use
+    /// [`crate::io::class::read_background`].
+    pub fn from_text(text: &str) -> Result<Self, anyhow::Error> {
+        let (z, H) = read_columns(text, 0, 1)?;
+        Self::new(
+            &z.into_iter().map(Redshift::new).collect::<Vec<_>>(),
+            &H.into_iter().map(KmPerSecPerMpc::new).collect::<Vec<_>>(),
+        )
+    }
+
+    /// Set a descriptive name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Set the curvature density at `z=0`, used for transverse distances.
+    pub fn with_curvature(mut self, omega_k0: f64) -> Self {
+        self.omega_k0 = DimensionlessFloat::new(omega_k0);
+        self
+    }
+
+    /// Set the matter density at `z=0`, used for matter densities.
+    pub fn with_omega_m0(mut self, omega_m0: f64) -> Self {
+        self.omega_m0 = Some(DimensionlessFloat::new(omega_m0));
+        self
+    }
+
+    /// Set the CMB temperature at `z=0`.
+    pub fn with_T_CMB0(mut self, T_CMB0: f64) -> Self {
+        self.T_CMB0 = Some(Kelvin::new(T_CMB0));
+        self
+    }
+
+    /// Largest tabulated redshift.
+    pub fn z_max(&self) -> Redshift {
+        Redshift::new(self.ln_E.x_max().exp() - 1.)
+    }
+
+    /// Mean (non-relativistic) matter density at redshift z, if `omega_m0` is known.
+    pub fn mean_matter_density(&self, z: Redshift) -> Option<KilogramPerMeter3> {
+        self.omega_m0.map(|omega_m0| {
+            KilogramPerMeter3::new(
+                omega_m0.0 * self.critical_density(Redshift::zero()).0 * (1.0 + z.0).powi(3),
+            )
+        })
+    }
+}
+
+impl Background for TabulatedCosmology {
+    fn E(&self, z: Redshift) -> DimensionlessPositiveFloat {
+        PositiveFloat(self.ln_E.eval((1. + z.0).ln()).exp())
+    }
+
+    fn H_0(&self) -> KmPerSecPerMpc {
+        self.H_0
+    }
+
+    fn omega_k0(&self) -> DimensionlessFloat {
+        self.omega_k0
+    }
+
+    fn T_CMB0(&self) -> Option<Kelvin> {
+        self.T_CMB0
+    }
+}
+
+/// Read two numeric columns of a whitespace separated table, skipping
+/// blank lines and `#` comments.
+pub(crate) fn read_columns(
+    text: &str,
+    x_column: usize,
+    y_column: usize,
+) -> Result<(Vec<f64>, Vec<f64>), anyhow::Error> {
+    let mut x = vec![];
+    let mut y = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let values: Vec<&str> = line.split_whitespace().collect();
+        let column = |c: usize| -> Result<f64, anyhow::Error> {
+            values
+                .get(c)
+                .ok_or_else(|| anyhow!("line {}: missing column {}", i + 1, c + 1))?
+                .parse::<f64>()
+                .map_err(|e| anyhow!("line {}: {}", i + 1, e))
+        };
+        x.push(column(x_column)?);
+        y.push(column(y_column)?);
+    }
+    Ok((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Distances, FLRWCosmology};
+
+    fn tabulate(cosmology: &FLRWCosmology, z_max: f64, n: usize) -> TabulatedCosmology {
+        let z: Vec<Redshift> = (0..=n)
+            .map(|i| Redshift::new(((1. + z_max).ln() * i as f64 / n as f64).exp() - 1.))
+            .collect();
+        let H: Vec<KmPerSecPerMpc> = z.iter().map(|z| cosmology.H(*z)).collect();
+        TabulatedCosmology::new(&z, &H).unwrap()
+    }
+
+    #[test]
+    fn matches_analytic_cosmology() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let tabulated = tabulate(&cosmology, 10., 100);
+        assert_eq!(tabulated.H_0, cosmology.H_0);
+
+        let z = Redshift::new(2.);
+        assert!(
+            (tabulated.H(Redshift::new(1.234)).0 / cosmology.H(Redshift::new(1.234)).0 - 1.).abs()
+                < 1e-6
+        );
+        let d_c = tabulated.radial_comoving_distance(z).0;
+        assert!((d_c / cosmology.radial_comoving_distance(z).0 - 1.).abs() < 1e-4);
+        assert!((tabulated.lookback_time(z).0 / cosmology.lookback_time(z).0 - 1.).abs() < 1e-4);
+        assert_eq!(
+            tabulated.critical_density(Redshift::zero()),
+            cosmology.critical_density(Redshift::zero())
+        );
+        assert!(tabulated.mean_matter_density(z).is_none());
+        assert!(tabulated
+            .with_omega_m0(0.3)
+            .mean_matter_density(z)
+            .is_some());
+    }
+
+    #[test]
+    fn reads_text_tables() {
+        let table = "
+            # z  H [km/s/Mpc]  extra
+            1.0  120.0  5
+            0.0  70.0   5
+
+            2.0  200.0  5
+        ";
+        let tabulated = TabulatedCosmology::from_text(table).unwrap();
+        assert_eq!(tabulated.H_0, KmPerSecPerMpc::new(70.));
+        assert!((tabulated.z_max().0 - 2.).abs() < 1e-12);
+        assert!((tabulated.H(Redshift::new(1.)).0 - 120.).abs() < 1e-9);
+
+        assert!(TabulatedCosmology::from_text("1.0 120.0\n2.0 200.0").is_err());
+        assert!(TabulatedCosmology::from_text("0.0 70.0\n1.0").is_err());
+    }
+}
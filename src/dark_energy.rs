@@ -1,7 +1,81 @@
-use crate::Redshift;
+use crate::{FLRWCosmology, Redshift};
 
 pub trait DarkEnergyEquationOfState {
     fn w(&self, z: Vec<Redshift>) -> Vec<f64>;
 }
 
-// TODO: impl DarkEnergyEquationOfState for FLRWCosmology
+/// Chevallier-Polarski-Linder parametrization of the dark energy equation
+/// of state, $w(z) = w_0 + w_a z / (1 + z)$.
+///
+/// The default is a cosmological constant, $w_0 = -1$ and $w_a = 0$.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CPLParameters {
+    /// Equation of state at `z=0`.
+    pub w_0: f64,
+    /// Derivative of the equation of state with respect to the scale factor.
+    pub w_a: f64,
+}
+
+impl Default for CPLParameters {
+    fn default() -> Self {
+        Self { w_0: -1., w_a: 0. }
+    }
+}
+
+impl CPLParameters {
+    pub fn new(w_0: f64, w_a: f64) -> Self {
+        Self { w_0, w_a }
+    }
+
+    /// Whether this is a cosmological constant.
+    pub fn is_cosmological_constant(&self) -> bool {
+        self.w_0 == -1. && self.w_a == 0.
+    }
+
+    /// Equation of state parameter at redshift z.
+    pub fn w(&self, z: Redshift) -> f64 {
+        self.w_0 + self.w_a * z.0 / (1. + z.0)
+    }
+
+    /// Dark energy density at redshift z relative to its value at `z=0`.
+    ///
+    /// Eqn. 3 from Linder 2003 (<https://arxiv.org/abs/astro-ph/0208512>)
+    pub fn density_scaling(&self, z: Redshift) -> f64 {
+        if self.is_cosmological_constant() {
+            return 1.;
+        }
+        (1. + z.0).powf(3. * (1. + self.w_0 + self.w_a)) * (-3. * self.w_a * z.0 / (1. + z.0)).exp()
+    }
+}
+
+impl DarkEnergyEquationOfState for FLRWCosmology {
+    fn w(&self, z: Vec<Redshift>) -> Vec<f64> {
+        z.into_iter().map(|z| self.dark_energy.w(z)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FloatingPointUnit;
+
+    #[test]
+    fn cosmological_constant() {
+        let lambda = CPLParameters::default();
+        assert!(lambda.is_cosmological_constant());
+        assert_eq!(lambda.w(Redshift::new(3.)), -1.);
+        assert_eq!(lambda.density_scaling(Redshift::new(3.)), 1.);
+    }
+
+    #[test]
+    fn constant_w_density_scaling() {
+        // For constant w, rho_DE scales as (1+z)^{3(1+w)}
+        let de = CPLParameters::new(-0.8, 0.);
+        let scaling = de.density_scaling(Redshift::new(1.));
+        assert!((scaling - 2_f64.powf(0.6)).abs() < 1e-12);
+
+        let cpl = CPLParameters::new(-0.9, 0.2);
+        assert!((cpl.w(Redshift::new(1.)) + 0.8).abs() < 1e-12);
+    }
+}
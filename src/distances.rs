@@ -1,15 +1,45 @@
+use anyhow::anyhow;
+
 use crate::{
     constants,
-    units::{FloatingPointUnit, Mpc3},
-    DimensionlessFloat, FLRWCosmology, Mpc, Redshift,
+    integrate::integrate,
+    inverse, kcorrection,
+    units::{
+        angle::{Arcminute, Arcsecond, Radian, SquareDegree, Steradian, FULL_SKY},
+        flux::{ErgPerSecPerCm2PerAngstrom, WattPerMeter2, WattPerMeter2PerHz},
+        length::{Kpc, Mpc3, MPC_TO_METERS},
+        luminosity::{ErgPerSecPerAngstrom, Watt, WattPerHz},
+        magnitude::{Magnitude, MagnitudeSystem},
+        FloatingPointUnit, HInvMpc, HInvMpc3, LittleHScaled,
+    },
+    Background, DimensionlessFloat, Mpc, Redshift,
 };
 
-/// Bin width in redshift integrals.
-const DZ: f64 = 0.0001;
+/// Where a redshift lies relative to the turning points of the transverse
+/// comoving distance $S_k(\chi)$ of a closed universe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurvatureRegion {
+    /// The transverse comoving distance increases with redshift, as it
+    /// always does in flat and open models.
+    Increasing,
+    /// Past $\chi = \pi R / 2$: the transverse comoving distance decreases
+    /// with redshift, so objects of a fixed size appear larger.
+    PastMaximum,
+    /// Past the antipode $\chi = \pi R$: $S_k(\chi)$ is negative and light
+    /// reaches us from the direction opposite to the source.
+    PastAntipode,
+}
 
 /// Cosmological distances following [Hogg 2000]
 /// [Hogg 2000]: <https://arxiv.org/pdf/astro-ph/9905116.pdf>
 pub trait Distances {
+    /// Radius of curvature of space today, $R = D_H / \sqrt{|\Omega_{k,0}|}$,
+    /// or `None` in a flat universe.
+    fn curvature_radius(&self) -> Option<Mpc>;
+    /// Transverse comoving distance $S_k(\chi)$ for the line of sight comoving
+    /// distance $\chi$: $R \sinh(\chi / R)$ in an open universe ($\Omega_{k,0} > 0$),
+    /// $\chi$ in a flat one and $R \sin(\chi / R)$ in a closed one.
+    fn sin_k(&self, chi: Mpc) -> Mpc;
     /// Line of sight (radial) comoving distance in Megaparsecs.
     fn radial_comoving_distance(&self, z: Redshift) -> Mpc;
     /// Transverse comoving distance in Megaparsecs.
@@ -19,54 +49,280 @@ pub trait Distances {
     /// Luminosity distance in Megaparsecs.
     ///
     /// This should be used with bolometric quantities, i.e.
-    /// it does not include K-corrections.
+    /// it does not include K-corrections (see [`crate::kcorrection`]).
     fn luminosity_distance(&self, z: Redshift) -> Mpc;
-    /// Comoving volume.
+    /// All sky comoving volume out to redshift `z`.
     fn comoving_volume(&self, z: Redshift) -> Mpc3;
+
+    /// Line of sight comoving distance between redshifts `z_1` and `z_2`,
+    /// positive when `z_2` is the more distant.
+    fn radial_comoving_distance_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc;
+    /// Angular diameter distance of a source at `z_2` as seen by an observer at `z_1`,
+    /// as needed for gravitational lensing.
+    ///
+    /// In curved models this is not the difference of the single-redshift
+    /// distances: $D_A(z_1, z_2) = S_k(\chi_2 - \chi_1) / (1+z_2)$.
+    fn angular_diameter_distance_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc;
+    /// Comoving volume of the all sky shell between redshifts `z_1` and `z_2`.
+    fn comoving_volume_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc3;
+
+    /// Redshift at which the transverse comoving distance of a closed
+    /// universe peaks, where $\chi = \pi R / 2$.
+    ///
+    /// `None` unless the universe is closed and $\chi$ reaches $\pi R / 2$
+    /// by [`inverse::DEFAULT_Z_MAX`].
+    fn maximum_transverse_comoving_distance_redshift(
+        &self,
+    ) -> Result<Option<Redshift>, anyhow::Error>;
+    /// Redshift of the antipode of a closed universe, where $\chi = \pi R$
+    /// and the angular diameter distance falls to zero.
+    ///
+    /// `None` unless the universe is closed and $\chi$ reaches $\pi R$
+    /// by [`inverse::DEFAULT_Z_MAX`].
+    fn antipode_redshift(&self) -> Result<Option<Redshift>, anyhow::Error>;
+    /// Where redshift `z` lies relative to the turning points of $S_k(\chi)$.
+    fn curvature_region(&self, z: Redshift) -> CurvatureRegion;
+    /// Differential comoving volume $dV_C / dz / d\Omega = D_H D_M^2 / E(z)$
+    /// per unit redshift per steradian.
+    fn differential_comoving_volume(&self, z: Redshift) -> Mpc3;
+
+    /// Differential comoving volume per unit redshift per square degree.
+    fn differential_comoving_volume_per_square_degree(&self, z: Redshift) -> Mpc3 {
+        let square_degree: Steradian = SquareDegree::new(1.).into();
+        Mpc3::new(self.differential_comoving_volume(z).0 * square_degree.0)
+    }
+
+    /// Angle subtended by an object of proper size `size` at redshift `z`.
+    ///
+    /// Uses the small angle approximation: $\theta = l / D_A$.
+    fn angular_size(&self, size: Mpc, z: Redshift) -> Radian {
+        Radian::new(size.0 / self.angular_diameter_distance(z).0)
+    }
+
+    /// Proper size of an object subtending `angle` at redshift `z`.
+    fn proper_size(&self, angle: Radian, z: Redshift) -> Mpc {
+        Mpc::new(angle.0 * self.angular_diameter_distance(z).0)
+    }
+
+    /// Proper size subtended by one arcsecond at redshift `z`.
+    fn kpc_per_arcsec_proper(&self, z: Redshift) -> Kpc {
+        self.proper_size(Arcsecond::new(1.).into(), z).into()
+    }
+
+    /// Comoving size subtended by one arcminute at redshift `z`.
+    fn kpc_per_arcmin_comoving(&self, z: Redshift) -> Kpc {
+        let angle: Radian = Arcminute::new(1.).into();
+        Mpc::new(angle.0 * self.transverse_comoving_distance(z).0).into()
+    }
+
+    /// Redshift at which an object of fixed proper size appears smallest,
+    /// i.e. at which the angular diameter distance peaks.
+    ///
+    /// In a closed universe this comes before the maximum of the transverse
+    /// comoving distance, see [`Distances::maximum_transverse_comoving_distance_redshift`].
+    ///
+    /// The redshift is searched for between [`inverse::DEFAULT_Z_MIN`] and
+    /// [`inverse::DEFAULT_Z_MAX`].
+    fn minimum_angular_size_redshift(&self) -> Result<Redshift, anyhow::Error> {
+        inverse::z_at_maximum_between(
+            |z| self.angular_diameter_distance(z),
+            Redshift::new(inverse::DEFAULT_Z_MIN),
+            Redshift::new(inverse::DEFAULT_Z_MAX),
+        )
+    }
+
+    /// Comoving volume within a sky area `area` out to redshift `z`.
+    fn comoving_volume_in_area(&self, z: Redshift, area: Steradian) -> Mpc3 {
+        Mpc3::new(self.comoving_volume(z).0 * sky_fraction(area))
+    }
+
+    /// Comoving volume of a survey covering the sky area `area` between
+    /// redshifts `z_min` and `z_max`.
+    ///
+    /// The area may be given in steradians or square degrees.
+    fn survey_volume<A: Into<Steradian>>(
+        &self,
+        area: A,
+        z_min: Redshift,
+        z_max: Redshift,
+    ) -> Result<Mpc3, anyhow::Error> {
+        Ok(self.survey_volumes(area, &[z_min, z_max])?[0])
+    }
+
+    /// Comoving volumes of a survey covering the sky area `area` in the
+    /// redshift bins between consecutive `edges`.
+    ///
+    /// The edges must be increasing. The comoving volume is computed once per edge.
+    fn survey_volumes<A: Into<Steradian>>(
+        &self,
+        area: A,
+        edges: &[Redshift],
+    ) -> Result<Vec<Mpc3>, anyhow::Error> {
+        if edges.len() < 2 {
+            return Err(anyhow!("at least two redshift bin edges are needed"));
+        }
+        if let Some(pair) = edges.windows(2).find(|pair| pair[1] <= pair[0]) {
+            return Err(anyhow!(
+                "redshift bin edges must be increasing, got {} then {}",
+                pair[0].0,
+                pair[1].0
+            ));
+        }
+        let fraction = sky_fraction(area.into());
+        let volumes: Vec<f64> = edges.iter().map(|&z| self.comoving_volume(z).0).collect();
+        Ok(volumes
+            .windows(2)
+            .map(|pair| Mpc3::new((pair[1] - pair[0]) * fraction))
+            .collect())
+    }
+
+    /// Distance modulus $\mu = 5 \log_{10}(d_L / 10\,\mathrm{pc})$ in magnitudes.
+    ///
+    /// This does not include K-corrections, so it relates bolometric
+    /// magnitudes, or magnitudes in the same rest frame band.
+    fn distance_modulus(&self, z: Redshift) -> DimensionlessFloat {
+        // 10 pc = 1e-5 Mpc
+        DimensionlessFloat::new(5. * (self.luminosity_distance(z).0 / 1e-5).log10())
+    }
+
+    /// Apparent magnitude of a source at redshift `z` with absolute magnitude `absolute`.
+    ///
+    /// See [`Distances::distance_modulus`].
+    fn apparent_magnitude<S: MagnitudeSystem>(
+        &self,
+        absolute: Magnitude<S>,
+        z: Redshift,
+    ) -> Magnitude<S> {
+        absolute + self.distance_modulus(z)
+    }
+
+    /// Absolute magnitude of a source at redshift `z` with apparent magnitude `apparent`.
+    ///
+    /// See [`Distances::distance_modulus`].
+    fn absolute_magnitude<S: MagnitudeSystem>(
+        &self,
+        apparent: Magnitude<S>,
+        z: Redshift,
+    ) -> Magnitude<S> {
+        apparent - self.distance_modulus(z)
+    }
+
+    /// Bolometric flux received from a source of bolometric `luminosity` at redshift `z`.
+    fn flux(&self, luminosity: Watt, z: Redshift) -> WattPerMeter2 {
+        WattPerMeter2::new(luminosity.0 / luminosity_area(self, z))
+    }
+
+    /// Bolometric luminosity of a source at redshift `z` with bolometric `flux`.
+    fn luminosity(&self, flux: WattPerMeter2, z: Redshift) -> Watt {
+        Watt::new(flux.0 * luminosity_area(self, z))
+    }
+
+    /// Flux observed in a band from a source at redshift `z` with luminosity
+    /// `luminosity` in the corresponding rest-frame band, given the
+    /// K-correction between the bands.
+    fn band_flux(
+        &self,
+        luminosity: Watt,
+        z: Redshift,
+        k_correction: DimensionlessFloat,
+    ) -> WattPerMeter2 {
+        WattPerMeter2::new(
+            luminosity.0
+                / (luminosity_area(self, z) * kcorrection::luminosity_factor(k_correction).0),
+        )
+    }
+
+    /// Luminosity in a rest-frame band of a source at redshift `z` with flux
+    /// `flux` observed in a band, given the K-correction between the bands.
+    fn band_luminosity(
+        &self,
+        flux: WattPerMeter2,
+        z: Redshift,
+        k_correction: DimensionlessFloat,
+    ) -> Watt {
+        Watt::new(
+            flux.0 * luminosity_area(self, z) * kcorrection::luminosity_factor(k_correction).0,
+        )
+    }
+
+    /// Spectral flux density observed at frequency $\nu$ from a source at
+    /// redshift `z`, given its spectral luminosity density at the emitted
+    /// frequency $(1+z) \nu$.
+    fn spectral_flux_density(&self, luminosity: WattPerHz, z: Redshift) -> WattPerMeter2PerHz {
+        WattPerMeter2PerHz::new((1. + z.0) * luminosity.0 / luminosity_area(self, z))
+    }
+
+    /// Spectral luminosity density at the emitted frequency $(1+z) \nu$ of a
+    /// source at redshift `z`, given its spectral flux density observed at $\nu$.
+    fn spectral_luminosity_density(&self, flux: WattPerMeter2PerHz, z: Redshift) -> WattPerHz {
+        WattPerHz::new(flux.0 * luminosity_area(self, z) / (1. + z.0))
+    }
+
+    /// Spectral flux density observed at wavelength $\lambda$ from a source at
+    /// redshift `z`, given its spectral luminosity density at the emitted
+    /// wavelength $\lambda / (1+z)$.
+    fn spectral_flux_density_per_angstrom(
+        &self,
+        luminosity: ErgPerSecPerAngstrom,
+        z: Redshift,
+    ) -> ErgPerSecPerCm2PerAngstrom {
+        // 1 m^2 = 1e4 cm^2
+        ErgPerSecPerCm2PerAngstrom::new(
+            luminosity.0 / ((1. + z.0) * luminosity_area(self, z) * 1e4),
+        )
+    }
+
+    /// Spectral luminosity density at the emitted wavelength $\lambda / (1+z)$
+    /// of a source at redshift `z`, given its spectral flux density observed at $\lambda$.
+    fn spectral_luminosity_density_per_angstrom(
+        &self,
+        flux: ErgPerSecPerCm2PerAngstrom,
+        z: Redshift,
+    ) -> ErgPerSecPerAngstrom {
+        ErgPerSecPerAngstrom::new(flux.0 * (1. + z.0) * luminosity_area(self, z) * 1e4)
+    }
+}
+
+/// Cosmological distances in units of h^{-1} Mpc.
+pub trait LittleHDistances: Distances + Background {
+    /// Line of sight (radial) comoving distance in h^{-1} Megaparsecs.
+    fn radial_comoving_distance_little_h(&self, z: Redshift) -> HInvMpc {
+        HInvMpc::from_unit(self.radial_comoving_distance(z), self.little_h())
+    }
+    /// Transverse comoving distance in h^{-1} Megaparsecs.
+    fn transverse_comoving_distance_little_h(&self, z: Redshift) -> HInvMpc {
+        HInvMpc::from_unit(self.transverse_comoving_distance(z), self.little_h())
+    }
+    /// Angular diameter distance in h^{-1} Megaparsecs.
+    fn angular_diameter_distance_little_h(&self, z: Redshift) -> HInvMpc {
+        HInvMpc::from_unit(self.angular_diameter_distance(z), self.little_h())
+    }
+    /// Luminosity distance in h^{-1} Megaparsecs.
+    fn luminosity_distance_little_h(&self, z: Redshift) -> HInvMpc {
+        HInvMpc::from_unit(self.luminosity_distance(z), self.little_h())
+    }
+    /// Comoving volume in h^{-3} Mpc^3.
+    fn comoving_volume_little_h(&self, z: Redshift) -> HInvMpc3 {
+        HInvMpc3::from_unit(self.comoving_volume(z), self.little_h())
+    }
 }
 
-impl Distances for FLRWCosmology {
+impl<T: Background> Distances for T {
+    fn curvature_radius(&self) -> Option<Mpc> {
+        curvature_radius(self.hubble_distance(), self.omega_k0())
+    }
+
+    fn sin_k(&self, chi: Mpc) -> Mpc {
+        sin_k(chi, self.hubble_distance(), self.omega_k0())
+    }
+
     fn radial_comoving_distance(&self, z: Redshift) -> Mpc {
-        // TODO: To speed up further pick x equal size bins
-        // We operate over 1e4
-        let max_range = (z.0 as i64) * 10000;
-        let step = DZ;
-        // Quantity inside the sqrt is E()
-        let integrand: f64 = (0..max_range)
-            .map(|z_prime_e4| {
-                step / (self.omega.Omega_M0.0 * (1. + z_prime_e4 as f64 / 10000.).powi(3)
-                    + self.omega_k0.0 * (1. + z_prime_e4 as f64 / 10000.).powi(2)
-                    + self.omega.Omega_DE0.0
-                    + (self.omega_gamma0.0 + self.omega_nu0.0)
-                        * (1. + z_prime_e4 as f64 / 10000.).powi(4))
-                .sqrt()
-            })
-            .sum();
-
-        Mpc::new(self.hubble_distance().0 * integrand)
+        let integral = integrate(|z_prime| 1. / self.E(Redshift::new(z_prime)).0, 0., z.0);
+        Mpc::new(self.hubble_distance().0 * integral)
     }
 
     fn transverse_comoving_distance(&self, z: Redshift) -> Mpc {
-        let radial_comoving = self.radial_comoving_distance(z);
-        let omega_k = self.omega_k(z);
-        if omega_k > DimensionlessFloat::zero() {
-            // Negative curvature (open)
-            let sqrt_omega_k = (omega_k.0).sqrt();
-            Mpc::new(
-                self.hubble_distance().0 * 1. / sqrt_omega_k
-                    * f64::sinh(sqrt_omega_k * radial_comoving.0 / self.hubble_distance().0),
-            )
-        } else if omega_k == DimensionlessFloat::zero() {
-            // Flat
-            radial_comoving
-        } else {
-            // Positive curvature (closed)
-            let abs_sqrt_omega_k = (-1. * omega_k.0).sqrt();
-            Mpc::new(
-                self.hubble_distance().0 * 1. / abs_sqrt_omega_k
-                    * f64::sin(abs_sqrt_omega_k * radial_comoving.0 / self.hubble_distance().0),
-            )
-        }
+        self.sin_k(self.radial_comoving_distance(z))
     }
 
     fn angular_diameter_distance(&self, z: Redshift) -> Mpc {
@@ -74,48 +330,161 @@ impl Distances for FLRWCosmology {
     }
 
     fn luminosity_distance(&self, z: Redshift) -> Mpc {
-        // TODO: K-CORRECTIONS
         Mpc::new(self.transverse_comoving_distance(z).0 * (1. + z.0))
     }
 
-    /// Comoving volume
     fn comoving_volume(&self, z: Redshift) -> Mpc3 {
-        // KmPerSecPerMpc
-        let d_H = self.hubble_distance().0;
-        let d_H_cubed = d_H.powi(3);
-
-        let transverse_comoving = self.transverse_comoving_distance(z);
-        let omega_k = self.omega_k(z);
-        if omega_k > DimensionlessFloat::zero() {
-            // Negative curvature (open)
-            let sqrt_omega_k = (omega_k.0).sqrt();
-            let coefficient = 4. * constants::PI * d_H_cubed / (2. * omega_k.0);
-            let term_1_in_parens = transverse_comoving.0 / d_H
-                * (1. + omega_k.0 * transverse_comoving.powi(2) / d_H.powi(2)).sqrt();
-            let term_2_in_parens =
-                1. / sqrt_omega_k * f64::asinh(sqrt_omega_k * transverse_comoving.0 / d_H);
-
-            coefficient * (term_1_in_parens - term_2_in_parens)
-        } else if omega_k == DimensionlessFloat::zero() {
-            // Flat
-            4. * constants::PI * transverse_comoving.powi(3) / 3.
+        comoving_volume(
+            self.radial_comoving_distance(z),
+            self.hubble_distance(),
+            self.omega_k0(),
+        )
+    }
+
+    fn radial_comoving_distance_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc {
+        let integral = integrate(
+            |z_prime| 1. / self.E(Redshift::new(z_prime)).0,
+            z_1.0,
+            z_2.0,
+        );
+        Mpc::new(self.hubble_distance().0 * integral)
+    }
+
+    fn angular_diameter_distance_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc {
+        let transverse = self.sin_k(self.radial_comoving_distance_between(z_1, z_2));
+        Mpc::new(transverse.0 / (1. + z_2.0))
+    }
+
+    fn comoving_volume_between(&self, z_1: Redshift, z_2: Redshift) -> Mpc3 {
+        Mpc3::new(self.comoving_volume(z_2).0 - self.comoving_volume(z_1).0)
+    }
+
+    fn differential_comoving_volume(&self, z: Redshift) -> Mpc3 {
+        let d_M = self.transverse_comoving_distance(z).0;
+        Mpc3::new(self.hubble_distance().0 * d_M * d_M / self.E(z).0)
+    }
+
+    fn maximum_transverse_comoving_distance_redshift(
+        &self,
+    ) -> Result<Option<Redshift>, anyhow::Error> {
+        redshift_at_closed_angle(self, constants::PI / 2.)
+    }
+
+    fn antipode_redshift(&self) -> Result<Option<Redshift>, anyhow::Error> {
+        redshift_at_closed_angle(self, constants::PI)
+    }
+
+    fn curvature_region(&self, z: Redshift) -> CurvatureRegion {
+        let radius = match closed_curvature_radius(self) {
+            Some(radius) => radius,
+            None => return CurvatureRegion::Increasing,
+        };
+        let angle = self.radial_comoving_distance(z).0 / radius.0;
+        if angle > constants::PI {
+            CurvatureRegion::PastAntipode
+        } else if angle > constants::PI / 2. {
+            CurvatureRegion::PastMaximum
         } else {
-            // Positive curvature (closed)
-            let sqrt_omega_k = (-1. * omega_k.0).sqrt();
-            let coefficient = 4. * constants::PI * d_H_cubed / (2. * omega_k.0);
-            let term_1_in_parens = transverse_comoving.0 / d_H
-                * (1. + omega_k.0 * transverse_comoving.powi(2) / d_H.powi(2)).sqrt();
-            let term_2_in_parens =
-                1. / sqrt_omega_k * f64::asin(sqrt_omega_k * transverse_comoving.0 / d_H);
-
-            coefficient * (term_1_in_parens - term_2_in_parens)
+            CurvatureRegion::Increasing
         }
     }
 }
 
+impl<T: Background> LittleHDistances for T {}
+
+/// Surface area $4 \pi d_L^2$ in square meters of the sphere over which
+/// light from redshift `z` is spread.
+pub(crate) fn luminosity_area<C: Distances + ?Sized>(cosmology: &C, z: Redshift) -> f64 {
+    4. * constants::PI * (cosmology.luminosity_distance(z).0 * MPC_TO_METERS).powi(2)
+}
+
+/// Fraction of the full sky covered by `area`.
+fn sky_fraction(area: Steradian) -> f64 {
+    area.0 / FULL_SKY.0
+}
+
+/// Curvature radius $R = D_H / \sqrt{|\Omega_{k,0}|}$, or `None` when flat.
+fn curvature_radius(hubble_distance: Mpc, omega_k0: DimensionlessFloat) -> Option<Mpc> {
+    if omega_k0 == DimensionlessFloat::zero() {
+        None
+    } else {
+        Some(Mpc::new(hubble_distance.0 / omega_k0.0.abs().sqrt()))
+    }
+}
+
+/// Curvature radius of a closed universe, or `None` for flat and open ones.
+fn closed_curvature_radius<T: Background>(cosmology: &T) -> Option<Mpc> {
+    if cosmology.omega_k0() < DimensionlessFloat::zero() {
+        cosmology.curvature_radius()
+    } else {
+        None
+    }
+}
+
+/// Redshift at which $\chi = \theta R$ in a closed universe, for the angle $\theta$ in radians.
+fn redshift_at_closed_angle<T: Background>(
+    cosmology: &T,
+    angle: f64,
+) -> Result<Option<Redshift>, anyhow::Error> {
+    let radius = match closed_curvature_radius(cosmology) {
+        Some(radius) => radius,
+        None => return Ok(None),
+    };
+    let target = Mpc::new(angle * radius.0);
+    let z_max = Redshift::new(inverse::DEFAULT_Z_MAX);
+    if cosmology.radial_comoving_distance(z_max) < target {
+        return Ok(None);
+    }
+    inverse::z_at_value(|z| cosmology.radial_comoving_distance(z), target).map(Some)
+}
+
+/// $S_k(\chi)$: $R \sinh(\chi / R)$ when open, $\chi$ when flat and
+/// $R \sin(\chi / R)$ when closed.
+fn sin_k(chi: Mpc, hubble_distance: Mpc, omega_k0: DimensionlessFloat) -> Mpc {
+    match curvature_radius(hubble_distance, omega_k0) {
+        None => chi,
+        Some(radius) if omega_k0.0 > 0. => Mpc::new(radius.0 * (chi.0 / radius.0).sinh()),
+        Some(radius) => Mpc::new(radius.0 * (chi.0 / radius.0).sin()),
+    }
+}
+
+/// All sky comoving volume within the comoving radius $\chi$, $4 \pi \int_0^\chi S_k^2 d\chi'$.
+///
+/// Written in terms of $\chi$ rather than $S_k(\chi)$, the volume keeps
+/// growing in closed models past $\chi = \pi R / 2$, where $S_k$ turns over.
+fn comoving_volume(chi: Mpc, hubble_distance: Mpc, omega_k0: DimensionlessFloat) -> Mpc3 {
+    match curvature_radius(hubble_distance, omega_k0) {
+        None => Mpc3::new(4. * constants::PI * chi.powi(3) / 3.),
+        Some(radius) => {
+            let y = 2. * chi.0 / radius.0;
+            let sign = omega_k0.0.signum();
+            Mpc3::new(constants::PI * radius.powi(3) * volume_series(y, sign))
+        }
+    }
+}
+
+/// $\sinh y - y$ for `sign` $= 1$ and $y - \sin y$ for `sign` $= -1$.
+///
+/// A series is used for small $y$, where the difference cancels.
+fn volume_series(y: f64, sign: f64) -> f64 {
+    if y.abs() < 0.1 {
+        let y2 = y * y;
+        y * y2 / 6. * (1. + sign * y2 / 20. * (1. + sign * y2 / 42. * (1. + sign * y2 / 72.)))
+    } else if sign > 0. {
+        y.sinh() - y
+    } else {
+        y - y.sin()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{cosmology::OmegaFactors, eV, units::PositiveFloat};
+    use crate::{
+        cosmology::OmegaFactors,
+        eV,
+        units::{luminosity::SolarLuminosity, magnitude::AB, PositiveFloat},
+        FLRWCosmology,
+    };
 
     use super::*;
 
@@ -188,9 +557,11 @@ mod tests {
         assert!(cosmology.radial_comoving_distance(Redshift::new(3.0)) < Mpc::new(5300.));
         assert!(cosmology.angular_diameter_distance(Redshift::new(3.0)) > Mpc::new(1250.));
         assert!(cosmology.angular_diameter_distance(Redshift::new(3.0)) < Mpc::new(1600.));
-        // No k-corrections here
-        assert!(cosmology.luminosity_distance(Redshift::new(3.0)) > Mpc::new(22000.));
-        assert!(cosmology.luminosity_distance(Redshift::new(3.0)) < Mpc::new(24000.));
+        // No k-corrections here. Curvature uses Omega_k0: d_L(3) is 24820.97 Mpc
+        // from an mpmath evaluation of the Hogg 2000 formulas, see
+        // `curved_distances_match_reference_values`.
+        assert!(cosmology.luminosity_distance(Redshift::new(3.0)) > Mpc::new(24800.));
+        assert!(cosmology.luminosity_distance(Redshift::new(3.0)) < Mpc::new(24850.));
     }
 
     #[test]
@@ -208,6 +579,184 @@ mod tests {
         assert!(cosmology.luminosity_distance(Redshift::new(2.0)) < Mpc::new(16000.));
     }
 
+    #[test]
+    fn no_big_bang_models_do_not_hang() {
+        // E^2 < 0 at z = 1, so there is no past light cone to integrate along
+        let cosmology = FLRWCosmology::two_component(0.3, 2.0, 70.);
+        let start = std::time::Instant::now();
+        assert!(cosmology
+            .radial_comoving_distance(Redshift::new(3.0))
+            .0
+            .is_nan());
+        assert!(cosmology.comoving_volume(Redshift::new(3.0)).0.is_nan());
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn curvature_geometry() {
+        let flat = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        assert_eq!(flat.curvature_radius(), None);
+        assert_eq!(flat.sin_k(Mpc::new(1000.)), Mpc::new(1000.));
+
+        let open = FLRWCosmology::two_component(0.3, 0.45, 70.);
+        let radius = open.curvature_radius().unwrap();
+        assert!((radius.0 - open.hubble_distance().0 / 0.5).abs() < 1e-9);
+        let chi = Mpc::new(radius.0);
+        assert!((open.sin_k(chi).0 / radius.0 - 1_f64.sinh()).abs() < 1e-12);
+
+        let closed = FLRWCosmology::two_component(0.3, 0.95, 70.);
+        let radius = closed.curvature_radius().unwrap();
+        assert!((radius.0 - closed.hubble_distance().0 / 0.5).abs() < 1e-9);
+        let chi = Mpc::new(radius.0 * constants::PI / 2.);
+        assert!((closed.sin_k(chi).0 - radius.0).abs() < 1e-9);
+
+        // Nearly flat models are continuous with the flat one
+        for omega_de in [0.7 - 1e-9, 0.7 + 1e-9] {
+            let nearly_flat = FLRWCosmology::two_component(0.3, omega_de, 70.);
+            let z = Redshift::new(2.);
+            let v = nearly_flat.comoving_volume(z).0 / flat.comoving_volume(z).0;
+            assert!((v - 1.).abs() < 1e-8);
+            let d = nearly_flat.transverse_comoving_distance(z).0;
+            assert!((d / flat.transverse_comoving_distance(z).0 - 1.).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn closed_universe_turning_points() {
+        let cosmology = FLRWCosmology::two_component(2., 3., 70.);
+        let radius = cosmology.curvature_radius().unwrap();
+
+        let z_max = cosmology
+            .maximum_transverse_comoving_distance_redshift()
+            .unwrap()
+            .unwrap();
+        assert!((cosmology.transverse_comoving_distance(z_max).0 / radius.0 - 1.).abs() < 1e-12);
+        let before = Redshift::new(z_max.0 * 0.99);
+        let after = Redshift::new(z_max.0 * 1.01);
+        assert!(cosmology.transverse_comoving_distance(before) < radius);
+        assert!(cosmology.transverse_comoving_distance(after) < radius);
+        // D_A peaks before D_M does
+        assert!(cosmology.minimum_angular_size_redshift().unwrap() < z_max);
+
+        let z_antipode = cosmology.antipode_redshift().unwrap().unwrap();
+        assert!(z_antipode > z_max);
+        assert!(cosmology.angular_diameter_distance(z_antipode).0.abs() < 1e-6 * radius.0);
+
+        assert_eq!(
+            cosmology.curvature_region(before),
+            CurvatureRegion::Increasing
+        );
+        assert_eq!(
+            cosmology.curvature_region(after),
+            CurvatureRegion::PastMaximum
+        );
+        let beyond = Redshift::new(z_antipode.0 * 1.01);
+        assert_eq!(
+            cosmology.curvature_region(beyond),
+            CurvatureRegion::PastAntipode
+        );
+        assert!(cosmology.transverse_comoving_distance(beyond) < Mpc::zero());
+
+        // Flat, open and only slightly closed models have no turning points
+        for omega_de in [0.7, 0.5, 0.71] {
+            let cosmology = FLRWCosmology::two_component(0.3, omega_de, 70.);
+            assert_eq!(cosmology.antipode_redshift().unwrap(), None);
+            let z = Redshift::new(100.);
+            assert_eq!(cosmology.curvature_region(z), CurvatureRegion::Increasing);
+        }
+    }
+
+    #[test]
+    fn curved_distances_match_reference_values() {
+        // TESTED vs: an independent 30 digit mpmath evaluation of the Hogg 2000
+        // formulas for D_M, D_L and V_C that astropy's LambdaCDM(H0, Om0, Ode0,
+        // Tcmb0=0) implements, without radiation.
+        let cases = [
+            // (H0, Om0, Ode0, z, D_M, D_L, V_C)
+            (
+                70.,
+                0.3,
+                0.5,
+                2.,
+                5051.33366192,
+                15154.0009858,
+                5.00533173057e11,
+            ),
+            (
+                70.,
+                0.3,
+                0.9,
+                2.,
+                5299.86707337,
+                15899.6012201,
+                6.92665715392e11,
+            ),
+            (
+                69.6,
+                0.286,
+                0.,
+                3.,
+                6205.24296745,
+                24820.9718698,
+                7.41308597647e11,
+            ),
+        ];
+        for (H_0, omega_m, omega_de, z, d_M, d_L, v_C) in cases {
+            let omegas = OmegaFactors::new(omega_m, omega_de, 0.05).unwrap();
+            let cosmology = FLRWCosmology::new(None, None, H_0, omegas, None, None, None).unwrap();
+            let z = Redshift::new(z);
+            assert!((cosmology.transverse_comoving_distance(z).0 / d_M - 1.).abs() < 1e-7);
+            assert!((cosmology.luminosity_distance(z).0 / d_L - 1.).abs() < 1e-7);
+            assert!((cosmology.comoving_volume(z).0 / v_C - 1.).abs() < 1e-7);
+        }
+    }
+
+    #[test]
+    fn curved_distances_match_mattig() {
+        // TESTED vs: the Mattig relation for matter only models,
+        // d_L = 2 D_H [Omega z + (Omega - 2)(sqrt(1 + Omega z) - 1)] / Omega^2
+        for omega_m in [0.3, 1., 3.] {
+            let cosmology = FLRWCosmology::two_component(omega_m, 0., 70.);
+            for z in [0.5, 2., 10.] {
+                let d_L = 2.
+                    * cosmology.hubble_distance().0
+                    * (omega_m * z + (omega_m - 2.) * ((1. + omega_m * z).sqrt() - 1.))
+                    / (omega_m * omega_m);
+                let computed = cosmology.luminosity_distance(Redshift::new(z)).0;
+                assert!((computed / d_L - 1.).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn closed_volume_past_the_equator() {
+        let cosmology = FLRWCosmology::two_component(10., 0., 70.);
+        let z = Redshift::new(10.);
+        let radius = cosmology.curvature_radius().unwrap();
+        // S_k has turned over, so the volume can't be found from D_M alone
+        assert!(cosmology.radial_comoving_distance(z).0 > radius.0 * constants::PI / 2.);
+
+        for z in [0.5, 2., 10.] {
+            let integral = integrate(
+                |z| cosmology.differential_comoving_volume(Redshift::new(z)).0,
+                0.,
+                z,
+            );
+            let volume = cosmology.comoving_volume(Redshift::new(z)).0;
+            assert!((volume / (4. * constants::PI * integral) - 1.).abs() < 1e-8);
+        }
+
+        // The open model is checked the same way
+        let cosmology = FLRWCosmology::two_component(0.3, 0., 70.);
+        let integral = integrate(
+            |z| cosmology.differential_comoving_volume(Redshift::new(z)).0,
+            0.,
+            3.,
+        );
+        let volume = cosmology.comoving_volume(Redshift::new(3.)).0;
+        assert!((volume / (4. * constants::PI * integral) - 1.).abs() < 1e-8);
+    }
+
     #[test]
     fn simple_two_component() {
         let cosmology = FLRWCosmology::two_component(0.286, 0.714, 69.6);
@@ -232,7 +781,230 @@ mod tests {
         // TESTED vs: astro.py 5.1 FlatLambdaCDM. Within 10e8 Mpc3.
         let omegas = OmegaFactors::new(0.27, 0.73, 0.044).unwrap();
         let cosmology = FLRWCosmology::new(None, None, 70.0, omegas, None, None, None).unwrap();
-        assert!(cosmology.comoving_volume(Redshift::new(3.0)) > 1179361698730.);
-        assert!(cosmology.comoving_volume(Redshift::new(3.0)) < 1179470000000.);
+        assert!(cosmology.comoving_volume(Redshift::new(3.0)) > Mpc3::new(1179361698730.));
+        assert!(cosmology.comoving_volume(Redshift::new(3.0)) < Mpc3::new(1179470000000.));
+    }
+
+    #[test]
+    fn differential_comoving_volume() {
+        // TESTED vs: astropy FlatLambdaCDM(H0=70, Om0=0.3): 2.655e10 Mpc3/sr at z=1
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let z = Redshift::new(1.0);
+        let dV = cosmology.differential_comoving_volume(z);
+        assert!((dV.0 / 2.655e10 - 1.).abs() < 1e-3);
+        let per_square_degree = cosmology.differential_comoving_volume_per_square_degree(z);
+        assert!((per_square_degree.0 * 41252.96 / (dV.0 * 4. * constants::PI) - 1.).abs() < 1e-6);
+
+        // All sky, it is the derivative of the comoving volume for every geometry
+        for (omega_m, omega_de) in [(0.3, 0.7), (0.3, 0.5), (0.3, 0.9)] {
+            let cosmology = FLRWCosmology::two_component(omega_m, omega_de, 70.);
+            for z in [0.1, 0.5, 2.] {
+                let dz = 1e-4;
+                let derivative = (cosmology.comoving_volume(Redshift::new(z + dz)).0
+                    - cosmology.comoving_volume(Redshift::new(z - dz)).0)
+                    / (2. * dz);
+                let dV = cosmology.differential_comoving_volume(Redshift::new(z));
+                assert!((4. * constants::PI * dV.0 / derivative - 1.).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn angular_size_and_sky_area() {
+        let cosmology = FLRWCosmology::two_component(0.286, 0.714, 69.6);
+        let z = Redshift::new(1.0);
+
+        let theta = cosmology.angular_size(Mpc::new(0.01), z);
+        let arcsec: Arcsecond = theta.into();
+        // ~8 kpc per arcsec at z=1 so 10 kpc is ~1.2 arcsec
+        assert!(arcsec > Arcsecond::new(1.1));
+        assert!(arcsec < Arcsecond::new(1.3));
+        assert!((cosmology.proper_size(theta, z).0 - 0.01).abs() < 1e-12);
+
+        let v = cosmology.comoving_volume(z);
+        let v_patch = cosmology.comoving_volume_in_area(z, SquareDegree::new(1000.).into());
+        assert!((v_patch.0 / v.0 - 1000. / 41252.96).abs() < 1e-6);
+        assert_eq!(cosmology.comoving_volume_in_area(z, FULL_SKY), v);
+    }
+
+    #[test]
+    fn survey_volumes() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let area = SquareDegree::new(5000.);
+        let edges: Vec<Redshift> = [0.2, 0.5, 1., 1.5].map(Redshift::new).to_vec();
+
+        let total = cosmology.survey_volume(area, edges[0], edges[3]).unwrap();
+        let shell = cosmology.comoving_volume_between(edges[0], edges[3]);
+        assert!((total.0 / shell.0 - 5000. / 41252.96).abs() < 1e-6);
+        let in_steradians: Steradian = area.into();
+        assert_eq!(
+            cosmology
+                .survey_volume(in_steradians, edges[0], edges[3])
+                .unwrap(),
+            total
+        );
+
+        let bins = cosmology.survey_volumes(area, &edges).unwrap();
+        assert_eq!(bins.len(), 3);
+        let sum: f64 = bins.iter().map(|v| v.0).sum();
+        assert!((sum / total.0 - 1.).abs() < 1e-12);
+        let first = cosmology.survey_volume(area, edges[0], edges[1]).unwrap();
+        assert!((bins[0].0 / first.0 - 1.).abs() < 1e-12);
+
+        assert!(cosmology.survey_volumes(area, &edges[..1]).is_err());
+        assert!(cosmology.survey_volume(area, edges[1], edges[0]).is_err());
+    }
+
+    #[test]
+    fn angular_scales() {
+        // TESTED vs: astropy FlatLambdaCDM(H0=70, Om0=0.3): 8.00 proper kpc/arcsec
+        // and 961 comoving kpc/arcmin at z=1
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let z = Redshift::new(1.0);
+        assert!((cosmology.kpc_per_arcsec_proper(z).0 - 8.00).abs() < 0.02);
+        assert!((cosmology.kpc_per_arcmin_comoving(z).0 - 961.).abs() < 1.);
+
+        // Einstein-de Sitter: D_A peaks at 1+z = 9/4
+        let cosmology = FLRWCosmology::two_component(1., 0., 70.);
+        let z_min = cosmology.minimum_angular_size_redshift().unwrap();
+        assert!((z_min.0 - 1.25).abs() < 1e-6);
+        let size = Mpc::new(0.01);
+        let theta = cosmology.angular_size(size, z_min);
+        for z in [1., 1.5] {
+            assert!(cosmology.angular_size(size, Redshift::new(z)) > theta);
+        }
+    }
+
+    #[test]
+    fn flux_and_luminosity() {
+        let cosmology = FLRWCosmology::two_component(0.286, 0.714, 69.6);
+        let z = Redshift::new(1.0);
+        let d_l_m = cosmology.luminosity_distance(z).0 * MPC_TO_METERS;
+
+        let l_sun: Watt = SolarLuminosity::new(1e10).into();
+        let flux = cosmology.flux(l_sun, z);
+        assert!((flux.0 * 4. * constants::PI * d_l_m.powi(2) / l_sun.0 - 1.).abs() < 1e-12);
+        assert!((cosmology.luminosity(flux, z).0 / l_sun.0 - 1.).abs() < 1e-12);
+
+        // A flat f_nu spectrum observed at z is brighter by (1+z) per unit
+        // frequency than the bolometric inverse square law suggests
+        let l_nu = WattPerHz::new(1e22);
+        let f_nu = cosmology.spectral_flux_density(l_nu, z);
+        assert!((f_nu.0 * 4. * constants::PI * d_l_m.powi(2) / l_nu.0 - 2.).abs() < 1e-12);
+        assert!((cosmology.spectral_luminosity_density(f_nu, z).0 / l_nu.0 - 1.).abs() < 1e-12);
+
+        let l_lambda = ErgPerSecPerAngstrom::new(1e40);
+        let f_lambda = cosmology.spectral_flux_density_per_angstrom(l_lambda, z);
+        let l_back = cosmology.spectral_luminosity_density_per_angstrom(f_lambda, z);
+        assert!((l_back.0 / l_lambda.0 - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn non_integer_redshifts() {
+        // Einstein-de Sitter: D_C = 2 D_H (1 - 1/sqrt(1+z))
+        let cosmology = FLRWCosmology::two_component(1., 0., 70.);
+        let z = Redshift::new(0.5);
+        let expected = 2. * cosmology.hubble_distance().0 * (1. - 1. / 1.5_f64.sqrt());
+        assert!((cosmology.radial_comoving_distance(z).0 / expected - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn distance_modulus_and_magnitudes() {
+        // TESTED vs: astropy FlatLambdaCDM(H0=70, Om0=0.3).distmod(1) = 44.10 mag
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let z = Redshift::new(1.0);
+        let mu = cosmology.distance_modulus(z);
+        assert!((mu.0 - 44.10).abs() < 0.01);
+        let d_L_pc = cosmology.luminosity_distance(z).0 * 1e6;
+        assert!((mu.0 - 5. * (d_L_pc / 10.).log10()).abs() < 1e-12);
+
+        let absolute = Magnitude::<AB>::new(-19.3);
+        let apparent = cosmology.apparent_magnitude(absolute, z);
+        assert!((apparent.0 - (mu.0 - 19.3)).abs() < 1e-12);
+        assert!((cosmology.absolute_magnitude(apparent, z).0 - absolute.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn k_corrected_band_luminosities() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let z = Redshift::new(1.0);
+        let luminosity = Watt::new(1e37);
+
+        // Without a K-correction the band behaves like the bolometric flux
+        let flux = cosmology.band_flux(luminosity, z, DimensionlessFloat::zero());
+        assert!((flux.0 / cosmology.flux(luminosity, z).0 - 1.).abs() < 1e-12);
+
+        // X-ray photon index 1.7: L = 4 pi d_L^2 F (1+z)^(Gamma-2)
+        let k = kcorrection::x_ray(1.7, z);
+        let flux = cosmology.band_flux(luminosity, z, k);
+        let expected = cosmology.flux(luminosity, z).0 / 2_f64.powf(-0.3);
+        assert!((flux.0 / expected - 1.).abs() < 1e-12);
+        assert!((cosmology.band_luminosity(flux, z, k).0 / luminosity.0 - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn distances_between_redshifts() {
+        let z_1 = Redshift::new(0.5);
+        let z_2 = Redshift::new(2.);
+
+        // Einstein-de Sitter: chi_12 = 2 D_H (1/sqrt(1+z_1) - 1/sqrt(1+z_2))
+        let cosmology = FLRWCosmology::two_component(1., 0., 70.);
+        let d_H = cosmology.hubble_distance().0;
+        let chi = 2. * d_H * (1. / 1.5_f64.sqrt() - 1. / 3_f64.sqrt());
+        assert!((cosmology.radial_comoving_distance_between(z_1, z_2).0 / chi - 1.).abs() < 1e-9);
+        assert!((cosmology.radial_comoving_distance_between(z_2, z_1).0 / chi + 1.).abs() < 1e-9);
+        let d_A = cosmology.angular_diameter_distance_between(z_1, z_2).0;
+        assert!((d_A / (chi / 3.) - 1.).abs() < 1e-9);
+
+        // Open: Hogg (2000) eq. 19 in terms of the single-redshift distances
+        let omegas = OmegaFactors::new(0.286, 0.0, 0.05).unwrap();
+        let open = FLRWCosmology::new(None, None, 69.6, omegas, None, None, None).unwrap();
+        let d_H = open.hubble_distance().0;
+        let omega_k = open.omega_k0().0;
+        let d_M_1 = open.transverse_comoving_distance(z_1).0;
+        let d_M_2 = open.transverse_comoving_distance(z_2).0;
+        let expected = (d_M_2 * (1. + omega_k * (d_M_1 / d_H).powi(2)).sqrt()
+            - d_M_1 * (1. + omega_k * (d_M_2 / d_H).powi(2)).sqrt())
+            / 3.;
+        let d_A = open.angular_diameter_distance_between(z_1, z_2).0;
+        assert!((d_A / expected - 1.).abs() < 1e-9);
+        assert!(d_A < d_M_2 / 3. - d_M_1 / 3.);
+
+        // From z=0 these reduce to the usual distances
+        let omegas = OmegaFactors::new(0.286, 0.8, 0.05).unwrap();
+        let closed = FLRWCosmology::new(None, None, 69.6, omegas, None, None, None).unwrap();
+        let z_0 = Redshift::zero();
+        assert!(
+            (closed.angular_diameter_distance_between(z_0, z_2).0
+                / closed.angular_diameter_distance(z_2).0
+                - 1.)
+                .abs()
+                < 1e-12
+        );
+
+        let shell = closed.comoving_volume_between(z_1, z_2);
+        assert!(
+            (shell.0 + closed.comoving_volume(z_1).0 - closed.comoving_volume(z_2).0).abs() < 1.
+        );
+        assert_eq!(
+            closed.comoving_volume_between(z_0, z_2),
+            closed.comoving_volume(z_2)
+        );
+    }
+
+    #[test]
+    fn little_h_distances() {
+        let omegas = OmegaFactors::new(0.27, 0.73, 0.044).unwrap();
+        let cosmology = FLRWCosmology::new(None, None, 70.0, omegas, None, None, None).unwrap();
+        let z = Redshift::new(1.0);
+
+        let d_c = cosmology.radial_comoving_distance(z);
+        let d_c_h = cosmology.radial_comoving_distance_little_h(z);
+        assert!((d_c_h.0 - 0.7 * d_c.0).abs() < 1e-9);
+        assert!((cosmology.remove_little_h(d_c_h).0 - d_c.0).abs() < 1e-9);
+
+        let v = cosmology.comoving_volume(z);
+        let v_h = cosmology.comoving_volume_little_h(z);
+        assert!((v_h.0 / v.0 - 0.7_f64.powi(3)).abs() < 1e-12);
     }
 }
@@ -0,0 +1,312 @@
+//! Attenuation by neutral hydrogen in the intergalactic medium (IGM).
+//!
+//! Light emitted blueward of Lyman $\alpha$ by a source at redshift
+//! $z_S$ is absorbed by the Lyman series lines and the Lyman continuum of
+//! intervening clouds. The models give the mean transmission $e^{-\tau}$
+//! as a function of observed wavelength, for observed wavelengths longward
+//! of the Lyman limit.
+use crate::{Angstrom, Redshift};
+
+/// Wavelength of the Lyman limit, $1 / R_H$.
+pub const LYMAN_LIMIT_ANGSTROM: f64 = 911.753;
+
+/// Lyman limit in Angstroms used by the continuum absorption of [Madau 1995].
+///
+/// [Madau 1995]: <https://ui.adsabs.harvard.edu/abs/1995ApJ...441...18M>
+const MADAU_1995_LYMAN_LIMIT: f64 = 912.;
+
+/// Lyman limit in Angstroms used by the continuum absorption of [Inoue et al. 2014].
+///
+/// [Inoue et al. 2014]: <https://arxiv.org/abs/1402.0677>
+const INOUE_2014_LYMAN_LIMIT: f64 = 911.8;
+
+/// Highest upper level of the Lyman series lines in [`IgmModel::Inoue2014`].
+const INOUE_2014_LINES: usize = 40;
+
+/// Lyman $\alpha$ to $\epsilon$ coefficients $A_j$ of [Madau 1995] eq. 15.
+///
+/// [Madau 1995]: <https://ui.adsabs.harvard.edu/abs/1995ApJ...441...18M>
+const MADAU_1995_COEFFICIENTS: [f64; 4] = [3.6e-3, 1.7e-3, 1.2e-3, 9.3e-4];
+
+/// Lyman-alpha forest coefficients $A^\mathrm{LAF}_{j,1}$ of [Inoue et al. 2014]
+/// Table 2, for upper levels $j = 2$ to 40.
+///
+/// [Inoue et al. 2014]: <https://arxiv.org/abs/1402.0677>
+const INOUE_2014_LAF_COEFFICIENTS: [f64; INOUE_2014_LINES - 1] = [
+    1.690e-2, 4.692e-3, 2.239e-3, 1.319e-3, 8.707e-4, 6.178e-4, 4.609e-4, 3.569e-4, 2.843e-4,
+    2.318e-4, 1.923e-4, 1.622e-4, 1.385e-4, 1.196e-4, 1.043e-4, 9.174e-5, 8.128e-5, 7.251e-5,
+    6.505e-5, 5.868e-5, 5.319e-5, 4.843e-5, 4.427e-5, 4.063e-5, 3.738e-5, 3.454e-5, 3.199e-5,
+    2.971e-5, 2.766e-5, 2.582e-5, 2.415e-5, 2.263e-5, 2.126e-5, 2.000e-5, 1.885e-5, 1.779e-5,
+    1.682e-5, 1.593e-5, 1.510e-5,
+];
+
+/// Damped Lyman-alpha system coefficients $A^\mathrm{DLA}_{j,1}$ of
+/// [Inoue et al. 2014] Table 2, for upper levels $j = 2$ to 40.
+///
+/// [Inoue et al. 2014]: <https://arxiv.org/abs/1402.0677>
+const INOUE_2014_DLA_COEFFICIENTS: [f64; INOUE_2014_LINES - 1] = [
+    1.617e-4, 1.545e-4, 1.498e-4, 1.460e-4, 1.429e-4, 1.402e-4, 1.377e-4, 1.355e-4, 1.335e-4,
+    1.316e-4, 1.298e-4, 1.281e-4, 1.265e-4, 1.250e-4, 1.236e-4, 1.222e-4, 1.209e-4, 1.197e-4,
+    1.185e-4, 1.173e-4, 1.162e-4, 1.151e-4, 1.140e-4, 1.130e-4, 1.120e-4, 1.110e-4, 1.101e-4,
+    1.091e-4, 1.082e-4, 1.073e-4, 1.065e-4, 1.056e-4, 1.048e-4, 1.040e-4, 1.032e-4, 1.024e-4,
+    1.017e-4, 1.009e-4, 1.002e-4,
+];
+
+/// A model of the mean IGM transmission.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IgmModel {
+    /// [Madau 1995]: power-law Lyman $\alpha$ to $\delta$ line blanketing
+    /// and the approximate Lyman continuum optical depth of footnote 3.
+    ///
+    /// [Madau 1995]: <https://ui.adsabs.harvard.edu/abs/1995ApJ...441...18M>
+    Madau1995,
+    /// [Inoue et al. 2014]: Lyman series to $j = 40$ and Lyman continuum
+    /// absorption by the Lyman-alpha forest and damped Lyman-alpha systems.
+    ///
+    /// [Inoue et al. 2014]: <https://arxiv.org/abs/1402.0677>
+    Inoue2014,
+}
+
+impl IgmModel {
+    /// Mean transmission at observed wavelength `wavelength` of light from a source at redshift `z`.
+    pub fn transmission(&self, wavelength: Angstrom, z: Redshift) -> f64 {
+        (-self.optical_depth(wavelength, z)).exp()
+    }
+
+    /// Effective optical depth at observed wavelength `wavelength` of the IGM
+    /// in front of a source at redshift `z`.
+    pub fn optical_depth(&self, wavelength: Angstrom, z: Redshift) -> f64 {
+        let (wavelength, z) = (wavelength.0, z.0);
+        match self {
+            IgmModel::Madau1995 => {
+                madau_1995_lines(wavelength, z) + madau_1995_continuum(wavelength, z)
+            }
+            IgmModel::Inoue2014 => {
+                inoue_2014_lines(wavelength, z)
+                    + inoue_2014_laf_continuum(wavelength, z)
+                    + inoue_2014_dla_continuum(wavelength, z)
+            }
+        }
+    }
+
+    /// Observed wavelengths in Angstroms at which the transmission of light
+    /// from redshift `z` is discontinuous.
+    pub(crate) fn edges(&self, z: Redshift) -> Vec<f64> {
+        // The continuum absorption cuts off at the Lyman limit of each model
+        let (lines, lyman_limit) = match self {
+            IgmModel::Madau1995 => (MADAU_1995_COEFFICIENTS.len(), MADAU_1995_LYMAN_LIMIT),
+            IgmModel::Inoue2014 => (INOUE_2014_LINES - 1, INOUE_2014_LYMAN_LIMIT),
+        };
+        (0..lines)
+            .map(lyman_line)
+            .chain([lyman_limit])
+            .map(|wavelength| wavelength * (1. + z.0))
+            .collect()
+    }
+}
+
+/// Wavelength of the Lyman series line with index `i`, i.e. upper level `i + 2`.
+fn lyman_line(i: usize) -> f64 {
+    let j = (i + 2) as f64;
+    LYMAN_LIMIT_ANGSTROM / (1. - 1. / j.powi(2))
+}
+
+fn madau_1995_lines(wavelength: f64, z: f64) -> f64 {
+    MADAU_1995_COEFFICIENTS
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            let line = lyman_line(i);
+            if wavelength > line && wavelength < line * (1. + z) {
+                a * (wavelength / line).powf(3.46)
+            } else {
+                0.
+            }
+        })
+        .sum()
+}
+
+fn madau_1995_continuum(wavelength: f64, z: f64) -> f64 {
+    let x_c = wavelength / MADAU_1995_LYMAN_LIMIT;
+    let x_em = 1. + z;
+    if x_c <= 1. || x_c >= x_em {
+        return 0.;
+    }
+    0.25 * x_c.powi(3) * (x_em.powf(0.46) - x_c.powf(0.46))
+        + 9.4 * x_c.powf(1.5) * (x_em.powf(0.18) - x_c.powf(0.18))
+        - 0.7 * x_c.powi(3) * (x_c.powf(-1.32) - x_em.powf(-1.32))
+        - 0.023 * (x_em.powf(1.68) - x_c.powf(1.68))
+}
+
+/// Lyman series absorption of [Inoue et al. 2014] eqs. 21 and 22.
+///
+/// The coefficients of each power law segment follow from continuity.
+fn inoue_2014_lines(wavelength: f64, z: f64) -> f64 {
+    INOUE_2014_LAF_COEFFICIENTS
+        .iter()
+        .zip(INOUE_2014_DLA_COEFFICIENTS)
+        .enumerate()
+        .map(|(i, (laf, dla))| {
+            let line = lyman_line(i);
+            if wavelength <= line || wavelength >= line * (1. + z) {
+                return 0.;
+            }
+            let x = wavelength / line;
+            let laf = if x < 2.2 {
+                laf * x.powf(1.2)
+            } else if x < 5.7 {
+                laf * 2.2_f64.powf(-2.5) * x.powf(3.7)
+            } else {
+                laf * 2.2_f64.powf(-2.5) * 5.7_f64.powf(-1.8) * x.powf(5.5)
+            };
+            let dla = if x < 3. {
+                dla * x.powi(2)
+            } else {
+                dla / 3. * x.powi(3)
+            };
+            laf + dla
+        })
+        .sum()
+}
+
+/// Lyman continuum absorption by the Lyman-alpha forest, [Inoue et al. 2014] eqs. 25 to 27.
+fn inoue_2014_laf_continuum(wavelength: f64, z: f64) -> f64 {
+    let x = wavelength / INOUE_2014_LYMAN_LIMIT;
+    let x_em = 1. + z;
+    if x <= 1. || x >= x_em {
+        return 0.;
+    }
+    if z < 1.2 {
+        0.325 * (x.powf(1.2) - x_em.powf(-0.9) * x.powf(2.1))
+    } else if z < 4.7 {
+        if x < 2.2 {
+            2.55e-2 * x_em.powf(1.6) * x.powf(2.1) + 0.325 * x.powf(1.2) - 0.250 * x.powf(2.1)
+        } else {
+            2.55e-2 * (x_em.powf(1.6) * x.powf(2.1) - x.powf(3.7))
+        }
+    } else if x < 2.2 {
+        5.22e-4 * x_em.powf(3.4) * x.powf(2.1) + 0.325 * x.powf(1.2) - 3.14e-2 * x.powf(2.1)
+    } else if x < 5.7 {
+        5.22e-4 * x_em.powf(3.4) * x.powf(2.1) + 0.218 * x.powf(2.1) - 2.55e-2 * x.powf(3.7)
+    } else {
+        5.22e-4 * (x_em.powf(3.4) * x.powf(2.1) - x.powf(5.5))
+    }
+}
+
+/// Lyman continuum absorption by damped Lyman-alpha systems, [Inoue et al. 2014] eqs. 28 and 29.
+fn inoue_2014_dla_continuum(wavelength: f64, z: f64) -> f64 {
+    let x = wavelength / INOUE_2014_LYMAN_LIMIT;
+    let x_em = 1. + z;
+    if x <= 1. || x >= x_em {
+        return 0.;
+    }
+    let tau = if z < 2. {
+        0.211 * x_em.powi(2) - 7.66e-2 * x_em.powf(2.3) * x.powf(-0.3) - 0.135 * x.powi(2)
+    } else if x < 3. {
+        0.634 + 4.70e-2 * x_em.powi(3)
+            - 1.78e-2 * x_em.powf(3.3) * x.powf(-0.3)
+            - 0.135 * x.powi(2)
+            - 0.291 * x.powf(-0.3)
+    } else {
+        4.70e-2 * x_em.powi(3) - 1.78e-2 * x_em.powf(3.3) * x.powf(-0.3) - 2.92e-2 * x.powi(3)
+    };
+    // The fits are slightly negative just below the Lyman limit of the source
+    tau.max(0.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FloatingPointUnit;
+
+    const MODELS: [IgmModel; 2] = [IgmModel::Madau1995, IgmModel::Inoue2014];
+
+    #[test]
+    fn lyman_series() {
+        assert!((lyman_line(0) - 1215.67).abs() < 0.01);
+        assert!((lyman_line(1) - 1025.72).abs() < 0.01);
+        assert!((lyman_line(38) - 912.324).abs() < 0.001);
+    }
+
+    #[test]
+    fn transparent_redward_of_lyman_alpha() {
+        let z = Redshift::new(3.);
+        for model in MODELS {
+            assert_eq!(model.transmission(Angstrom::new(1216. * 4.), z), 1.);
+            assert_eq!(
+                model.transmission(Angstrom::new(8000.), Redshift::zero()),
+                1.
+            );
+        }
+    }
+
+    #[test]
+    fn lyman_alpha_forest_and_break() {
+        // Between Lyman alpha and beta at z=3 the models agree on a ~35% decrement
+        let z = Redshift::new(3.);
+        let forest = Angstrom::new(1100. * 4.);
+        let madau = IgmModel::Madau1995.transmission(forest, z);
+        let inoue = IgmModel::Inoue2014.transmission(forest, z);
+        assert!(madau > 0.5 && madau < 0.8);
+        assert!(inoue > 0.5 && inoue < 0.8);
+        assert!((madau - inoue).abs() < 0.1);
+
+        // The forest thickens with redshift
+        for model in MODELS {
+            let t_3 = model.transmission(Angstrom::new(1100. * 4.), z);
+            let t_5 = model.transmission(Angstrom::new(1100. * 6.), Redshift::new(5.));
+            assert!(t_5 < t_3);
+        }
+
+        // Little escapes below the Lyman limit at z=4
+        let z = Redshift::new(4.);
+        for model in MODELS {
+            assert!(model.transmission(Angstrom::new(800. * 5.), z) < 0.1);
+        }
+    }
+
+    #[test]
+    fn inoue_continuum_is_continuous() {
+        for z in [1., 3., 6.] {
+            let x_em = 1. + z;
+            for x in [2.2, 3., 5.7] {
+                if x >= x_em {
+                    continue;
+                }
+                let below = INOUE_2014_LYMAN_LIMIT * x * (1. - 1e-9);
+                let above = INOUE_2014_LYMAN_LIMIT * x * (1. + 1e-9);
+                // Up to the rounding of the published coefficients
+                for tau in [inoue_2014_laf_continuum, inoue_2014_dla_continuum] {
+                    let jump = tau(below, z) - tau(above, z);
+                    assert!(jump.abs() < 1e-2 * tau(above, z).max(1.));
+                }
+            }
+            // Vanishing at the Lyman limit of the source
+            let edge = INOUE_2014_LYMAN_LIMIT * x_em * (1. - 1e-9);
+            assert!(inoue_2014_laf_continuum(edge, z).abs() < 1e-6);
+            assert!(inoue_2014_dla_continuum(edge, z).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn continuum_edges() {
+        let z = Redshift::new(3.);
+        for (model, lyman_limit) in [
+            (IgmModel::Madau1995, MADAU_1995_LYMAN_LIMIT),
+            (IgmModel::Inoue2014, INOUE_2014_LYMAN_LIMIT),
+        ] {
+            let edge = *model.edges(z).last().unwrap();
+            assert_eq!(edge, lyman_limit * 4.);
+            // No continuum absorption just above the edge
+            let above = Angstrom::new(edge * (1. + 1e-9));
+            let continuum = |wavelength: Angstrom| match model {
+                IgmModel::Madau1995 => madau_1995_continuum(wavelength.0, z.0),
+                IgmModel::Inoue2014 => {
+                    inoue_2014_laf_continuum(wavelength.0, z.0)
+                        + inoue_2014_dla_continuum(wavelength.0, z.0)
+                }
+            };
+            assert_eq!(continuum(above), 0.);
+        }
+    }
+}
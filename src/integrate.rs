@@ -0,0 +1,163 @@
+//! Numerical integration of smooth functions.
+
+/// Relative tolerance of [`integrate`].
+const RELATIVE_TOLERANCE: f64 = 1e-10;
+
+/// Maximum depth of interval bisection in [`integrate`].
+const MAX_DEPTH: u32 = 30;
+
+/// Maximum number of intervals evaluated by [`integrate`], each with 15
+/// evaluations of the integrand, so a hard integrand cannot recurse exponentially.
+const MAX_INTERVALS: usize = 100_000;
+
+/// Nodes of the 15 point Kronrod rule on `[-1, 1]` from QUADPACK; the odd
+/// entries are the nodes of the embedded 7 point Gauss rule.
+#[allow(clippy::excessive_precision)]
+const KRONROD_NODES: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.000000000000000000000000000000000,
+];
+
+/// Weights of the 15 point Kronrod rule.
+#[allow(clippy::excessive_precision)]
+const KRONROD_WEIGHTS: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+/// Weights of the 7 point Gauss rule.
+#[allow(clippy::excessive_precision)]
+const GAUSS_WEIGHTS: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+/// Integrate `f` over `[a, b]` with adaptive Gauss-Kronrod quadrature.
+///
+/// The interval is bisected until the difference between the 7 point Gauss
+/// and 15 point Kronrod estimates is within tolerance. The Kronrod estimate
+/// is returned, which is typically far more accurate than that difference,
+/// so the result varies smoothly with the limits of integration.
+///
+/// A non-finite estimate, e.g. from an integrand that is NaN somewhere in
+/// the interval, is returned as is rather than refined.
+pub(crate) fn integrate<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> f64 {
+    if a == b {
+        return 0.;
+    }
+    let (whole, _) = gauss_kronrod(&f, a, b);
+    if !whole.is_finite() {
+        return whole;
+    }
+    let tolerance = RELATIVE_TOLERANCE * whole.abs().max(f64::MIN_POSITIVE);
+    let mut remaining_intervals = MAX_INTERVALS - 1;
+    adaptive(&f, a, b, tolerance, MAX_DEPTH, &mut remaining_intervals)
+}
+
+fn adaptive<F: Fn(f64) -> f64>(
+    f: &F,
+    a: f64,
+    b: f64,
+    tolerance: f64,
+    depth: u32,
+    remaining_intervals: &mut usize,
+) -> f64 {
+    let (kronrod, error) = gauss_kronrod(f, a, b);
+    if depth == 0
+        || error <= tolerance
+        || !kronrod.is_finite()
+        || !error.is_finite()
+        || *remaining_intervals < 2
+    {
+        kronrod
+    } else {
+        *remaining_intervals -= 2;
+        let m = 0.5 * (a + b);
+        let left = adaptive(f, a, m, tolerance / 2., depth - 1, remaining_intervals);
+        left + adaptive(f, m, b, tolerance / 2., depth - 1, remaining_intervals)
+    }
+}
+
+/// The 15 point Kronrod estimate of the integral and its difference from
+/// the 7 point Gauss estimate.
+fn gauss_kronrod<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> (f64, f64) {
+    let center = 0.5 * (a + b);
+    let half_length = 0.5 * (b - a);
+
+    let f_center = f(center);
+    let mut kronrod = KRONROD_WEIGHTS[7] * f_center;
+    let mut gauss = GAUSS_WEIGHTS[3] * f_center;
+    for (i, (node, weight)) in KRONROD_NODES
+        .iter()
+        .zip(KRONROD_WEIGHTS)
+        .take(7)
+        .enumerate()
+    {
+        let dx = half_length * node;
+        let pair = f(center - dx) + f(center + dx);
+        kronrod += weight * pair;
+        if i % 2 == 1 {
+            gauss += GAUSS_WEIGHTS[i / 2] * pair;
+        }
+    }
+
+    (
+        kronrod * half_length,
+        ((kronrod - gauss) * half_length).abs(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn polynomials_and_transcendentals() {
+        assert!((integrate(|x| x.powi(3), 0., 2.) - 4.).abs() < 1e-12);
+        assert!((integrate(f64::sin, 0., std::f64::consts::PI) - 2.).abs() < 1e-9);
+        assert!((integrate(|x| 1. / x, 1., 1e3) - 1e3_f64.ln()).abs() < 1e-8);
+        assert_eq!(integrate(f64::exp, 1., 1.), 0.);
+        assert!((integrate(|x| x, 1., 0.) + 0.5).abs() < 1e-12);
+        assert!((integrate(f64::sqrt, 0., 1.) - 2. / 3.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn non_finite_and_hard_integrands_terminate() {
+        let evaluations = Cell::new(0_usize);
+        let nan_inside = |x: f64| {
+            evaluations.set(evaluations.get() + 1);
+            if x > 0.5 {
+                f64::NAN
+            } else {
+                1.
+            }
+        };
+        assert!(integrate(nan_inside, 0., 1.).is_nan());
+        assert!(evaluations.get() <= 30);
+
+        // Never converges, so the number of intervals is capped
+        evaluations.set(0);
+        let noise = |x: f64| {
+            evaluations.set(evaluations.get() + 1);
+            (1e9 * x).sin().signum()
+        };
+        assert!(integrate(noise, 0., 1.).is_finite());
+        assert!(evaluations.get() <= 15 * (MAX_INTERVALS + 1));
+    }
+}
@@ -0,0 +1,280 @@
+//! Interpolation of tabulated functions.
+use anyhow::anyhow;
+
+/// A natural cubic spline through points `(x, y)`.
+///
+/// Outside of the tabulated range the spline is extrapolated linearly using
+/// the slope at the nearest end point.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CubicSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// Second derivatives at each point.
+    d2y: Vec<f64>,
+}
+
+impl CubicSpline {
+    /// Build a spline through points with strictly increasing `x`.
+    pub(crate) fn new(x: Vec<f64>, y: Vec<f64>) -> Result<Self, anyhow::Error> {
+        if x.len() != y.len() {
+            return Err(anyhow!("x and y must have the same length"));
+        }
+        if x.len() < 2 {
+            return Err(anyhow!("at least two points are required to interpolate"));
+        }
+        if x.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(anyhow!("x must be strictly increasing"));
+        }
+        if x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+            return Err(anyhow!("cannot interpolate non-finite values"));
+        }
+
+        // Solve the tridiagonal system for the second derivatives with
+        // d2y = 0 at both ends
+        let n = x.len();
+        let mut d2y = vec![0.; n];
+        let mut u = vec![0.; n];
+        for i in 1..n - 1 {
+            let sig = (x[i] - x[i - 1]) / (x[i + 1] - x[i - 1]);
+            let p = sig * d2y[i - 1] + 2.;
+            d2y[i] = (sig - 1.) / p;
+            let slope_difference =
+                (y[i + 1] - y[i]) / (x[i + 1] - x[i]) - (y[i] - y[i - 1]) / (x[i] - x[i - 1]);
+            u[i] = (6. * slope_difference / (x[i + 1] - x[i - 1]) - sig * u[i - 1]) / p;
+        }
+        d2y[n - 1] = 0.;
+        for i in (0..n - 1).rev() {
+            d2y[i] = d2y[i] * d2y[i + 1] + u[i];
+        }
+
+        Ok(Self { x, y, d2y })
+    }
+
+    /// Largest tabulated `x`.
+    pub(crate) fn x_max(&self) -> f64 {
+        self.x[self.x.len() - 1]
+    }
+
+    /// Interpolated value at `x`.
+    pub(crate) fn eval(&self, x: f64) -> f64 {
+        let n = self.x.len();
+        if x < self.x[0] {
+            return self.y[0] + (x - self.x[0]) * self.derivative(0, 0.);
+        }
+        if x > self.x[n - 1] {
+            return self.y[n - 1] + (x - self.x[n - 1]) * self.derivative(n - 2, 1.);
+        }
+
+        let i = self.interval(x);
+        let h = self.x[i + 1] - self.x[i];
+        let a = (self.x[i + 1] - x) / h;
+        let b = (x - self.x[i]) / h;
+        a * self.y[i]
+            + b * self.y[i + 1]
+            + ((a.powi(3) - a) * self.d2y[i] + (b.powi(3) - b) * self.d2y[i + 1]) * h.powi(2) / 6.
+    }
+
+    /// Index `i` of the interval `[x_i, x_{i+1}]` containing `x`.
+    fn interval(&self, x: f64) -> usize {
+        self.x
+            .partition_point(|xi| *xi <= x)
+            .clamp(1, self.x.len() - 1)
+            - 1
+    }
+
+    /// First derivative in interval `i` at fractional position `t` in `[0, 1]`.
+    fn derivative(&self, i: usize, t: f64) -> f64 {
+        let h = self.x[i + 1] - self.x[i];
+        let (a, b) = (1. - t, t);
+        (self.y[i + 1] - self.y[i]) / h - (3. * a.powi(2) - 1.) / 6. * h * self.d2y[i]
+            + (3. * b.powi(2) - 1.) / 6. * h * self.d2y[i + 1]
+    }
+}
+
+/// A monotone piecewise cubic Hermite interpolant through points `(x, y)`.
+///
+/// Unlike [`CubicSpline`] it does not overshoot, so it is monotonic wherever
+/// the data are. It is only evaluated within the tabulated range.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MonotoneSpline {
+    x: Vec<f64>,
+    y: Vec<f64>,
+    /// First derivatives at each point.
+    dy: Vec<f64>,
+}
+
+impl MonotoneSpline {
+    /// Build an interpolant through points with strictly increasing `x`.
+    pub(crate) fn new(x: Vec<f64>, y: Vec<f64>) -> Result<Self, anyhow::Error> {
+        if x.len() != y.len() {
+            return Err(anyhow!("x and y must have the same length"));
+        }
+        if x.len() < 2 {
+            return Err(anyhow!("at least two points are required to interpolate"));
+        }
+        if x.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(anyhow!("x must be strictly increasing"));
+        }
+
+        let n = x.len();
+        let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let slopes: Vec<f64> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+        let mut dy = vec![0.; n];
+        for i in 1..n - 1 {
+            if slopes[i - 1] * slopes[i] > 0. {
+                // Three point estimate, limited so that the interpolant
+                // stays monotonic (Fritsch & Carlson 1980)
+                let d = (h[i] * slopes[i - 1] + h[i - 1] * slopes[i]) / (h[i - 1] + h[i]);
+                let limit = 3. * slopes[i - 1].abs().min(slopes[i].abs());
+                dy[i] = d.signum() * d.abs().min(limit);
+            }
+        }
+        dy[0] = end_derivative(h[0], h.get(1).copied(), slopes[0], slopes.get(1).copied());
+        dy[n - 1] = end_derivative(
+            h[n - 2],
+            n.checked_sub(3).map(|i| h[i]),
+            slopes[n - 2],
+            n.checked_sub(3).map(|i| slopes[i]),
+        );
+
+        Ok(Self { x, y, dy })
+    }
+
+    /// Interpolated value at `x`, or `None` outside the tabulated range.
+    pub(crate) fn eval(&self, x: f64) -> Option<f64> {
+        let n = self.x.len();
+        if !(x >= self.x[0] && x <= self.x[n - 1]) {
+            return None;
+        }
+        let i = self.x.partition_point(|xi| *xi <= x).clamp(1, n - 1) - 1;
+        let h = self.x[i + 1] - self.x[i];
+        let t = (x - self.x[i]) / h;
+        let (t2, t3) = (t * t, t * t * t);
+        Some(
+            (2. * t3 - 3. * t2 + 1.) * self.y[i]
+                + (t3 - 2. * t2 + t) * h * self.dy[i]
+                + (-2. * t3 + 3. * t2) * self.y[i + 1]
+                + (t3 - t2) * h * self.dy[i + 1],
+        )
+    }
+}
+
+/// Linear interpolation between points `(x, y)`.
+///
+/// It is only evaluated within the tabulated range.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LinearInterpolation {
+    x: Vec<f64>,
+    y: Vec<f64>,
+}
+
+impl LinearInterpolation {
+    /// Build an interpolant through points with strictly increasing `x`.
+    pub(crate) fn new(x: Vec<f64>, y: Vec<f64>) -> Result<Self, anyhow::Error> {
+        if x.len() != y.len() {
+            return Err(anyhow!("x and y must have the same length"));
+        }
+        if x.len() < 2 {
+            return Err(anyhow!("at least two points are required to interpolate"));
+        }
+        if x.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(anyhow!("x must be strictly increasing"));
+        }
+        if x.iter().chain(y.iter()).any(|v| !v.is_finite()) {
+            return Err(anyhow!("cannot interpolate non-finite values"));
+        }
+        Ok(Self { x, y })
+    }
+
+    /// Tabulated `x`.
+    pub(crate) fn x(&self) -> &[f64] {
+        &self.x
+    }
+
+    /// Interpolated value at `x`, or `None` outside the tabulated range.
+    pub(crate) fn eval(&self, x: f64) -> Option<f64> {
+        let n = self.x.len();
+        if !(x >= self.x[0] && x <= self.x[n - 1]) {
+            return None;
+        }
+        let i = self.x.partition_point(|xi| *xi <= x).clamp(1, n - 1) - 1;
+        let t = (x - self.x[i]) / (self.x[i + 1] - self.x[i]);
+        Some((1. - t) * self.y[i] + t * self.y[i + 1])
+    }
+}
+
+/// One-sided three point estimate of the derivative at an end point,
+/// limited to preserve monotonicity.
+fn end_derivative(h0: f64, h1: Option<f64>, s0: f64, s1: Option<f64>) -> f64 {
+    let (h1, s1) = match (h1, s1) {
+        (Some(h1), Some(s1)) => (h1, s1),
+        _ => return s0,
+    };
+    let d = ((2. * h0 + h1) * s0 - h0 * s1) / (h0 + h1);
+    if d * s0 <= 0. {
+        0.
+    } else if s0 * s1 <= 0. && d.abs() > 3. * s0.abs() {
+        3. * s0
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_smooth_functions() {
+        let x: Vec<f64> = (0..=40).map(|i| i as f64 * 0.1).collect();
+        let y: Vec<f64> = x.iter().map(|x| x.sin()).collect();
+        let spline = CubicSpline::new(x, y).unwrap();
+
+        assert_eq!(spline.eval(0.), 0.);
+        for x in [0.05, 1.234, 2.5, 3.45] {
+            assert!((spline.eval(x) - f64::sin(x)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn extrapolates_linearly() {
+        let spline = CubicSpline::new(vec![0., 1., 2.], vec![1., 3., 5.]).unwrap();
+        assert!((spline.eval(3.) - 7.).abs() < 1e-12);
+        assert!((spline.eval(-1.) + 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_invalid_tables() {
+        assert!(CubicSpline::new(vec![0.], vec![1.]).is_err());
+        assert!(CubicSpline::new(vec![0., 0.], vec![1., 2.]).is_err());
+        assert!(CubicSpline::new(vec![0., 1.], vec![1.]).is_err());
+    }
+
+    #[test]
+    fn linear_interpolation() {
+        let linear = LinearInterpolation::new(vec![0., 1., 3.], vec![1., 3., 2.]).unwrap();
+        assert_eq!(linear.eval(0.5), Some(2.));
+        assert_eq!(linear.eval(2.), Some(2.5));
+        assert_eq!(linear.eval(3.), Some(2.));
+        assert_eq!(linear.eval(-0.1), None);
+        assert!(LinearInterpolation::new(vec![0., 1.], vec![1., f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn monotone_interpolation_does_not_overshoot() {
+        let x = vec![0., 1., 2., 3., 4.];
+        let y = vec![0., 0., 0., 1., 1.];
+        let spline = MonotoneSpline::new(x, y).unwrap();
+        let values: Vec<f64> = (0..=400)
+            .map(|i| spline.eval(i as f64 * 0.01).unwrap())
+            .collect();
+        assert!(values.windows(2).all(|w| w[1] >= w[0]));
+        assert!(values.iter().all(|v| (0. ..=1.).contains(v)));
+        assert_eq!(spline.eval(4.5), None);
+
+        let x: Vec<f64> = (0..=20).map(|i| i as f64 * 0.1).collect();
+        let y: Vec<f64> = x.iter().map(|x| x.exp()).collect();
+        let spline = MonotoneSpline::new(x, y).unwrap();
+        assert!((spline.eval(1.05).unwrap() - 1.05_f64.exp()).abs() < 1e-3);
+    }
+}
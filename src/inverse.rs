@@ -0,0 +1,658 @@
+//! Redshifts at which a quantity takes a given value.
+use std::marker::PhantomData;
+
+use anyhow::anyhow;
+
+use crate::{
+    interpolate::MonotoneSpline, Background, DimensionlessFloat, Distances, FloatingPointUnit, Gyr,
+    Mpc, Redshift,
+};
+
+/// Default lower end of the redshift range searched by [`z_at_value`].
+pub const DEFAULT_Z_MIN: f64 = 0.;
+
+/// Default upper end of the redshift range searched by [`z_at_value`].
+pub const DEFAULT_Z_MAX: f64 = 1000.;
+
+/// Number of intervals the range is split into when bracketing a solution.
+const BRACKET_SAMPLES: usize = 200;
+
+/// Relative tolerance on the redshift found.
+const Z_TOLERANCE: f64 = 1e-12;
+
+/// Relative tolerance on the redshift of a maximum, which can only be
+/// located to about the square root of the precision of the function.
+const EXTREMUM_TOLERANCE: f64 = 1e-8;
+
+/// Maximum number of iterations of Brent's method and golden section search.
+const MAX_ITERATIONS: usize = 200;
+
+/// Default accuracy in redshift of a [`RedshiftTable`].
+pub const DEFAULT_TABLE_TOLERANCE: f64 = 1e-8;
+
+/// Number of intervals a [`RedshiftTable`] starts from before refinement.
+const INITIAL_TABLE_INTERVALS: usize = 64;
+
+/// Largest number of points in a [`RedshiftTable`].
+const MAX_TABLE_POINTS: usize = 1_000_000;
+
+/// Find the redshift at which `f(z)` equals `target`, searching between
+/// [`DEFAULT_Z_MIN`] and [`DEFAULT_Z_MAX`].
+///
+/// See [`z_at_value_between`].
+///
+/// # Examples
+///
+/// ```
+/// use cosmocalc::{inverse::z_at_value, Distances, FLRWCosmology, Mpc, FloatingPointUnit};
+///
+/// let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+/// let z = z_at_value(|z| cosmology.luminosity_distance(z), Mpc::new(1000.)).unwrap();
+/// ```
+pub fn z_at_value<F, U>(f: F, target: U) -> Result<Redshift, anyhow::Error>
+where
+    F: Fn(Redshift) -> U,
+    U: FloatingPointUnit,
+{
+    z_at_value_between(
+        f,
+        target,
+        Redshift::new(DEFAULT_Z_MIN),
+        Redshift::new(DEFAULT_Z_MAX),
+    )
+}
+
+/// Find the redshift between `z_min` and `z_max` at which `f(z)` equals `target`.
+///
+/// The range is scanned on a grid uniform in $\ln(1+z)$ to bracket the
+/// solution, which is then refined with Brent's method. `f` need not be
+/// monotonic over the whole range, but the solution must be unique: if `f`
+/// crosses `target` more than once the error reports roughly where, so the
+/// range can be narrowed. An error is also returned if `target` lies outside
+/// the values `f` takes in the range.
+pub fn z_at_value_between<F, U>(
+    f: F,
+    target: U,
+    z_min: Redshift,
+    z_max: Redshift,
+) -> Result<Redshift, anyhow::Error>
+where
+    F: Fn(Redshift) -> U,
+    U: FloatingPointUnit,
+{
+    if !(z_min.0 > -1. && z_max.0 > z_min.0) {
+        return Err(anyhow!("invalid redshift range [{}, {}]", z_min.0, z_max.0));
+    }
+    let target = target.inner();
+    let residual = |z: f64| f(Redshift::new(z)).inner() - target;
+
+    let grid = bracket_grid(z_min, z_max);
+    let values: Vec<f64> = grid.iter().map(|z| residual(*z)).collect();
+    if values.iter().any(|v| v.is_nan()) {
+        return Err(anyhow!(
+            "function is not defined everywhere in [{}, {}]",
+            z_min.0,
+            z_max.0
+        ));
+    }
+
+    // Solutions either lie exactly on the grid or between grid points where
+    // the residual changes sign
+    let exact: Vec<usize> = (0..values.len()).filter(|i| values[*i] == 0.).collect();
+    let brackets: Vec<usize> = (0..values.len() - 1)
+        .filter(|i| values[*i] * values[*i + 1] < 0.)
+        .collect();
+
+    match (exact.as_slice(), brackets.as_slice()) {
+        ([], []) => {
+            let (low, high) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), v| {
+                    (low.min(*v), high.max(*v))
+                });
+            Err(anyhow!(
+                "target {} is outside the range of values [{}, {}] in [{}, {}]",
+                target,
+                low + target,
+                high + target,
+                z_min.0,
+                z_max.0
+            ))
+        }
+        ([i], []) => Ok(Redshift::new(grid[*i])),
+        ([], [i]) => brent(
+            &residual,
+            grid[*i],
+            grid[*i + 1],
+            values[*i],
+            values[*i + 1],
+        ),
+        _ => Err(anyhow!(
+            "function is not monotonic: target {} is reached more than once, near z = {}; \
+             narrow the redshift range",
+            target,
+            exact
+                .iter()
+                .map(|i| format!("{:.4}", grid[*i]))
+                .chain(
+                    brackets
+                        .iter()
+                        .map(|i| format!("{:.4}", 0.5 * (grid[*i] + grid[*i + 1])))
+                )
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Find the redshift between `z_min` and `z_max` at which `f(z)` is largest.
+///
+/// The range is scanned on a grid uniform in $\ln(1+z)$ and the largest
+/// value refined with a golden section search. An error is returned if the
+/// maximum lies at either end of the range, where `f` may still be rising.
+pub fn z_at_maximum_between<F, U>(
+    f: F,
+    z_min: Redshift,
+    z_max: Redshift,
+) -> Result<Redshift, anyhow::Error>
+where
+    F: Fn(Redshift) -> U,
+    U: FloatingPointUnit,
+{
+    if !(z_min.0 > -1. && z_max.0 > z_min.0) {
+        return Err(anyhow!("invalid redshift range [{}, {}]", z_min.0, z_max.0));
+    }
+    let value = |z: f64| f(Redshift::new(z)).inner();
+
+    let grid = bracket_grid(z_min, z_max);
+    let values: Vec<f64> = grid.iter().map(|z| value(*z)).collect();
+    if values.iter().any(|v| v.is_nan()) {
+        return Err(anyhow!(
+            "function is not defined everywhere in [{}, {}]",
+            z_min.0,
+            z_max.0
+        ));
+    }
+    let i = (0..values.len())
+        .reduce(|best, i| if values[i] > values[best] { i } else { best })
+        .unwrap_or(0);
+    if i == 0 || i == values.len() - 1 {
+        return Err(anyhow!(
+            "maximum lies at the end of the redshift range [{}, {}]",
+            z_min.0,
+            z_max.0
+        ));
+    }
+
+    // Golden section search between the neighbours of the largest value
+    let ratio = (5_f64.sqrt() - 1.) / 2.;
+    let (mut a, mut b) = (grid[i - 1], grid[i + 1]);
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (value(c), value(d));
+    for _ in 0..MAX_ITERATIONS {
+        if b - a <= EXTREMUM_TOLERANCE * (1. + a.abs()) {
+            break;
+        }
+        if fc > fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - ratio * (b - a);
+            fc = value(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + ratio * (b - a);
+            fd = value(d);
+        }
+    }
+    Ok(Redshift::new(0.5 * (a + b)))
+}
+
+/// A tabulated inverse of a monotonic function of redshift, for converting
+/// many values (e.g. a catalog of distances) to redshifts at once.
+///
+/// The function is tabulated on a uniform grid in $\ln(1+z)$ and
+/// interpolated with a monotone cubic. The grid is halved until, at the
+/// midpoint of every interval, the interpolated redshift is within the
+/// requested tolerance of the true one.
+///
+/// # Examples
+///
+/// ```
+/// use cosmocalc::{inverse::RedshiftTable, FLRWCosmology, Mpc, Redshift, FloatingPointUnit};
+///
+/// let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+/// let table = RedshiftTable::luminosity_distance(&cosmology, Redshift::new(3.)).unwrap();
+/// let distances: Vec<Mpc> = (1..1000).map(|i| Mpc::new(i as f64 * 10.)).collect();
+/// let z = table.redshifts(&distances).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RedshiftTable<U> {
+    /// $z$ as a function of the (increasing) tabulated value.
+    spline: MonotoneSpline,
+    /// `1` if the function increases with redshift, `-1` if it decreases.
+    direction: f64,
+    /// Range of values covered by the table.
+    range: (f64, f64),
+    unit: PhantomData<U>,
+}
+
+impl<U: FloatingPointUnit> RedshiftTable<U> {
+    /// Tabulate the inverse of `f` between `z_min` and `z_max` to an accuracy
+    /// of `tolerance` in redshift.
+    ///
+    /// `f` must be strictly monotonic over the range.
+    pub fn new<F>(
+        f: F,
+        z_min: Redshift,
+        z_max: Redshift,
+        tolerance: f64,
+    ) -> Result<Self, anyhow::Error>
+    where
+        F: Fn(Redshift) -> U,
+    {
+        if !(z_min.0 > -1. && z_max.0 > z_min.0) {
+            return Err(anyhow!("invalid redshift range [{}, {}]", z_min.0, z_max.0));
+        }
+        if tolerance.is_nan() || tolerance <= 0. {
+            return Err(anyhow!("tolerance must be positive"));
+        }
+        let value = |z: f64| f(Redshift::new(z)).inner();
+
+        let ln_min = (1. + z_min.0).ln();
+        let ln_max = (1. + z_max.0).ln();
+        let mut ln_z1: Vec<f64> = (0..=INITIAL_TABLE_INTERVALS)
+            .map(|i| ln_min + (ln_max - ln_min) * i as f64 / INITIAL_TABLE_INTERVALS as f64)
+            .collect();
+        let mut values: Vec<f64> = ln_z1.iter().map(|x| value(x.exp_m1())).collect();
+        let direction = if values[ln_z1.len() - 1] > values[0] {
+            1.
+        } else {
+            -1.
+        };
+
+        loop {
+            if let Some(i) = (0..values.len() - 1).find(|i| {
+                let step = direction * (values[i + 1] - values[*i]);
+                step.is_nan() || step <= 0.
+            }) {
+                return Err(anyhow!(
+                    "function is not strictly monotonic between z = {} and z = {}",
+                    ln_z1[i].exp_m1(),
+                    ln_z1[i + 1].exp_m1()
+                ));
+            }
+            let spline = MonotoneSpline::new(
+                values.iter().map(|v| direction * v).collect(),
+                ln_z1.iter().map(|x| x.exp_m1()).collect(),
+            )?;
+
+            // Check the interpolation at the midpoint of every interval. If
+            // it fails anywhere the midpoints become nodes of the next table.
+            let ln_mid: Vec<f64> = ln_z1.windows(2).map(|w| 0.5 * (w[0] + w[1])).collect();
+            let mid: Vec<f64> = ln_mid.iter().map(|x| value(x.exp_m1())).collect();
+            let accurate = ln_mid.iter().zip(&mid).all(|(ln_z, value)| {
+                let error = spline
+                    .eval(direction * value)
+                    .map_or(f64::NAN, |z| (z - ln_z.exp_m1()).abs());
+                error <= tolerance
+            });
+
+            if accurate {
+                let (first, last) = (values[0], values[values.len() - 1]);
+                return Ok(Self {
+                    spline,
+                    direction,
+                    range: (first.min(last), first.max(last)),
+                    unit: PhantomData,
+                });
+            }
+            if 2 * ln_z1.len() - 1 > MAX_TABLE_POINTS {
+                return Err(anyhow!(
+                    "could not reach a tolerance of {} with {} points",
+                    tolerance,
+                    MAX_TABLE_POINTS
+                ));
+            }
+            ln_z1 = interleave(&ln_z1, &ln_mid);
+            values = interleave(&values, &mid);
+        }
+    }
+
+    /// Range of values that can be converted to redshifts.
+    pub fn range(&self) -> (U, U) {
+        (U::new(self.range.0), U::new(self.range.1))
+    }
+
+    /// Redshift at which the tabulated function takes `value`.
+    pub fn redshift(&self, value: U) -> Result<Redshift, anyhow::Error> {
+        self.spline
+            .eval(self.direction * value.inner())
+            .map(Redshift::new)
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} is outside of the tabulated range [{}, {}]",
+                    value.inner(),
+                    self.range.0,
+                    self.range.1
+                )
+            })
+    }
+
+    /// Redshifts at which the tabulated function takes each of `values`.
+    pub fn redshifts(&self, values: &[U]) -> Result<Vec<Redshift>, anyhow::Error>
+    where
+        U: Copy,
+    {
+        values.iter().map(|value| self.redshift(*value)).collect()
+    }
+}
+
+impl RedshiftTable<Mpc> {
+    /// Tabulate redshift as a function of luminosity distance up to `z_max`.
+    pub fn luminosity_distance<C: Distances>(
+        cosmology: &C,
+        z_max: Redshift,
+    ) -> Result<Self, anyhow::Error> {
+        Self::new(
+            |z| cosmology.luminosity_distance(z),
+            Redshift::zero(),
+            z_max,
+            DEFAULT_TABLE_TOLERANCE,
+        )
+    }
+
+    /// Tabulate redshift as a function of radial comoving distance up to `z_max`.
+    pub fn radial_comoving_distance<C: Distances>(
+        cosmology: &C,
+        z_max: Redshift,
+    ) -> Result<Self, anyhow::Error> {
+        Self::new(
+            |z| cosmology.radial_comoving_distance(z),
+            Redshift::zero(),
+            z_max,
+            DEFAULT_TABLE_TOLERANCE,
+        )
+    }
+}
+
+impl RedshiftTable<DimensionlessFloat> {
+    /// Tabulate redshift as a function of the distance modulus between
+    /// `z_min` and `z_max`.
+    ///
+    /// The distance modulus diverges at $z = 0$, so `z_min` must be positive.
+    pub fn distance_modulus<C: Distances>(
+        cosmology: &C,
+        z_min: Redshift,
+        z_max: Redshift,
+    ) -> Result<Self, anyhow::Error> {
+        if z_min.0 <= 0. {
+            return Err(anyhow!(
+                "the distance modulus table must start at a positive redshift, got {}",
+                z_min.0
+            ));
+        }
+        Self::new(
+            |z| cosmology.distance_modulus(z),
+            z_min,
+            z_max,
+            DEFAULT_TABLE_TOLERANCE,
+        )
+    }
+}
+
+impl RedshiftTable<Gyr> {
+    /// Tabulate redshift as a function of lookback time up to `z_max`.
+    pub fn lookback_time<C: Background>(
+        cosmology: &C,
+        z_max: Redshift,
+    ) -> Result<Self, anyhow::Error> {
+        Self::new(
+            |z| cosmology.lookback_time(z),
+            Redshift::zero(),
+            z_max,
+            DEFAULT_TABLE_TOLERANCE,
+        )
+    }
+}
+
+/// Grid of [`BRACKET_SAMPLES`] intervals between `z_min` and `z_max`,
+/// uniform in $\ln(1+z)$.
+fn bracket_grid(z_min: Redshift, z_max: Redshift) -> Vec<f64> {
+    let ln_min = (1. + z_min.0).ln();
+    let ln_max = (1. + z_max.0).ln();
+    (0..=BRACKET_SAMPLES)
+        .map(|i| {
+            if i == BRACKET_SAMPLES {
+                z_max.0
+            } else {
+                (ln_min + (ln_max - ln_min) * i as f64 / BRACKET_SAMPLES as f64).exp_m1()
+            }
+        })
+        .map(|z| if z < z_min.0 { z_min.0 } else { z })
+        .collect()
+}
+
+/// Alternate the elements of `nodes` with those of `midpoints`, which has
+/// one fewer element.
+fn interleave(nodes: &[f64], midpoints: &[f64]) -> Vec<f64> {
+    let mut merged = Vec::with_capacity(nodes.len() + midpoints.len());
+    for (node, midpoint) in nodes.iter().zip(midpoints) {
+        merged.push(*node);
+        merged.push(*midpoint);
+    }
+    merged.extend(nodes.last());
+    merged
+}
+
+/// Brent's method for the root of `f` in `[a, b]` where `f(a)` and `f(b)`
+/// have opposite signs.
+fn brent<F: Fn(f64) -> f64>(
+    f: &F,
+    mut a: f64,
+    mut b: f64,
+    mut fa: f64,
+    mut fb: f64,
+) -> Result<Redshift, anyhow::Error> {
+    let (mut c, mut fc) = (b, fb);
+    let (mut d, mut e) = (b - a, b - a);
+
+    for _ in 0..MAX_ITERATIONS {
+        if fb * fc > 0. {
+            // Keep the root between b and c
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+
+        let tolerance = 2. * f64::EPSILON * b.abs() + 0.5 * Z_TOLERANCE * (1. + b.abs());
+        let midpoint = 0.5 * (c - b);
+        if midpoint.abs() <= tolerance || fb == 0. {
+            return Ok(Redshift::new(b));
+        }
+
+        if e.abs() >= tolerance && fa.abs() > fb.abs() {
+            // Attempt inverse quadratic interpolation
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2. * midpoint * s, 1. - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2. * midpoint * q * (q - r) - (b - a) * (r - 1.)),
+                    (q - 1.) * (r - 1.) * (s - 1.),
+                )
+            };
+            if p > 0. {
+                q = -q;
+            }
+            p = p.abs();
+            if 2. * p < (3. * midpoint * q - (tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                // Interpolation failed, use bisection
+                d = midpoint;
+                e = d;
+            }
+        } else {
+            d = midpoint;
+            e = d;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tolerance {
+            d
+        } else {
+            tolerance.copysign(midpoint)
+        };
+        fb = f(b);
+    }
+
+    Err(anyhow!(
+        "no convergence after {} iterations",
+        MAX_ITERATIONS
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DimensionlessFloat, FLRWCosmology};
+
+    #[test]
+    fn inverts_distances_and_times() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+
+        for z in [0.01, 0.5, 1., 3.7] {
+            let d_l = cosmology.luminosity_distance(Redshift::new(z));
+            let found = z_at_value(|z| cosmology.luminosity_distance(z), d_l).unwrap();
+            assert!((found.0 - z).abs() < 1e-9);
+        }
+
+        let t = cosmology.lookback_time(Redshift::new(2.));
+        let found = z_at_value(|z| cosmology.lookback_time(z), t).unwrap();
+        assert!((found.0 - 2.).abs() < 1e-9);
+        assert_eq!(
+            z_at_value(|z| cosmology.luminosity_distance(z), Mpc::zero()).unwrap(),
+            Redshift::zero()
+        );
+    }
+
+    #[test]
+    fn out_of_range_target() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        // The lookback time can never exceed the age of the universe
+        let error = z_at_value(|z| cosmology.lookback_time(z), Gyr::new(20.)).unwrap_err();
+        assert!(error.to_string().contains("outside the range"));
+        assert!(z_at_value_between(
+            |z| cosmology.lookback_time(z),
+            Gyr::new(1.),
+            Redshift::new(2.),
+            Redshift::new(1.)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn non_monotonic_function() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        // The angular diameter distance peaks near z = 1.6
+        let d_a = cosmology.angular_diameter_distance(Redshift::new(1.));
+        let error = z_at_value(|z| cosmology.angular_diameter_distance(z), d_a).unwrap_err();
+        assert!(error.to_string().contains("not monotonic"));
+
+        let found = z_at_value_between(
+            |z| cosmology.angular_diameter_distance(z),
+            d_a,
+            Redshift::zero(),
+            Redshift::new(1.5),
+        )
+        .unwrap();
+        assert!((found.0 - 1.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn maximum_of_a_function() {
+        let peaked = |z: Redshift| Mpc::new(-(z.0 - 1.5).powi(2));
+        let z = z_at_maximum_between(peaked, Redshift::zero(), Redshift::new(10.)).unwrap();
+        assert!((z.0 - 1.5).abs() < 1e-7);
+
+        let rising = |z: Redshift| Mpc::new(z.0);
+        assert!(z_at_maximum_between(rising, Redshift::zero(), Redshift::new(10.)).is_err());
+        assert!(z_at_maximum_between(peaked, Redshift::new(2.), Redshift::new(1.)).is_err());
+    }
+
+    #[test]
+    fn tabulated_inverse() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let table = RedshiftTable::luminosity_distance(&cosmology, Redshift::new(5.)).unwrap();
+
+        let z: Vec<Redshift> = (0..=50).map(|i| Redshift::new(i as f64 * 0.1)).collect();
+        let d_l: Vec<Mpc> = z
+            .iter()
+            .map(|z| cosmology.luminosity_distance(*z))
+            .collect();
+        for (found, z) in table.redshifts(&d_l).unwrap().iter().zip(z) {
+            assert!((found.0 - z.0).abs() < 1e-7);
+        }
+        assert!(table.redshift(Mpc::new(-1.)).is_err());
+        assert!(table.redshift(Mpc::new(1e6)).is_err());
+
+        // A catalog of distance moduli, e.g. from supernovae
+        let table =
+            RedshiftTable::distance_modulus(&cosmology, Redshift::new(0.01), Redshift::new(2.))
+                .unwrap();
+        let z: Vec<Redshift> = (1..=200).map(|i| Redshift::new(i as f64 * 0.01)).collect();
+        let mu: Vec<DimensionlessFloat> =
+            z.iter().map(|z| cosmology.distance_modulus(*z)).collect();
+        for (found, z) in table.redshifts(&mu).unwrap().iter().zip(z) {
+            assert!((found.0 - z.0).abs() < 1e-7);
+        }
+        assert!(table.redshift(DimensionlessFloat::new(30.)).is_err());
+        assert!(
+            RedshiftTable::distance_modulus(&cosmology, Redshift::zero(), Redshift::new(2.))
+                .is_err()
+        );
+
+        // Lookback time and other decreasing functions
+        let table = RedshiftTable::lookback_time(&cosmology, Redshift::new(10.)).unwrap();
+        let t = cosmology.lookback_time(Redshift::new(2.5));
+        assert!((table.redshift(t).unwrap().0 - 2.5).abs() < 1e-7);
+        let table = RedshiftTable::new(
+            |z| DimensionlessFloat::new(cosmology.scale_factor(z).0),
+            Redshift::zero(),
+            Redshift::new(10.),
+            DEFAULT_TABLE_TOLERANCE,
+        )
+        .unwrap();
+        let z = table.redshift(DimensionlessFloat::new(0.25)).unwrap();
+        assert!((z.0 - 3.).abs() < 1e-7);
+    }
+
+    #[test]
+    fn tabulated_inverse_requires_monotonic_function() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        assert!(RedshiftTable::new(
+            |z| cosmology.angular_diameter_distance(z),
+            Redshift::zero(),
+            Redshift::new(5.),
+            DEFAULT_TABLE_TOLERANCE,
+        )
+        .is_err());
+    }
+}
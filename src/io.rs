@@ -0,0 +1,86 @@
+//! Reading and writing cosmologies in formats used by other codes.
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+
+pub mod astropy;
+pub mod camb;
+pub mod class;
+
+/// Conversion between the sum of neutrino masses and their physical density:
+/// $\Omega_\nu h^2 = \sum m_\nu / 93.14 \textrm{eV}$.
+pub const NEUTRINO_MASS_PER_OMEGA_NU_H2: f64 = 93.14;
+
+/// Physical density $\Omega_\nu h^2$ today of neutrinos with masses `m_nu`,
+/// which CAMB and CLASS subtract when closing the energy budget.
+pub(crate) fn massive_neutrino_density(m_nu: &[crate::eV]) -> f64 {
+    m_nu.iter().map(|m| m.0).sum::<f64>() / NEUTRINO_MASS_PER_OMEGA_NU_H2
+}
+
+/// Parameters of an `.ini` file as used by CAMB and CLASS.
+pub(crate) struct IniParameters(BTreeMap<String, String>);
+
+impl IniParameters {
+    /// Parse `key = value` lines, skipping blank lines and `#` comments.
+    pub(crate) fn parse(ini: &str) -> Self {
+        Self(
+            ini.lines()
+                .map(|line| line.split('#').next().unwrap_or_default())
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .filter(|(_, value)| !value.is_empty())
+                .collect(),
+        )
+    }
+
+    /// The first of `keys` that is present, as a number.
+    pub(crate) fn number(&self, keys: &[&str]) -> Result<Option<f64>, anyhow::Error> {
+        for key in keys {
+            if let Some(value) = self.0.get(*key) {
+                return value
+                    .parse::<f64>()
+                    .map(Some)
+                    .map_err(|e| anyhow!("invalid {} '{}': {}", key, value, e));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Like [`IniParameters::number`] but the parameter is required.
+    pub(crate) fn required(&self, keys: &[&str]) -> Result<f64, anyhow::Error> {
+        self.number(keys)?
+            .ok_or_else(|| anyhow!("missing parameter {}", keys.join(" or ")))
+    }
+
+    /// A list of numbers separated by commas and/or whitespace.
+    pub(crate) fn numbers(&self, key: &str) -> Result<Vec<f64>, anyhow::Error> {
+        self.0.get(key).map_or(Ok(vec![]), |value| {
+            value
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .map(|v| {
+                    v.parse::<f64>()
+                        .map_err(|e| anyhow!("invalid {} '{}': {}", key, value, e))
+                })
+                .collect()
+        })
+    }
+
+    /// A Fortran style boolean (`T`/`F`).
+    pub(crate) fn boolean(&self, key: &str) -> Option<bool> {
+        self.0.get(key).map(|value| {
+            matches!(
+                value.to_lowercase().as_str(),
+                "t" | "true" | ".true." | "yes"
+            )
+        })
+    }
+}
+
+/// Format `key = value` lines of an `.ini` file.
+pub(crate) fn format_ini(parameters: &[(&str, String)]) -> String {
+    parameters
+        .iter()
+        .map(|(key, value)| format!("{} = {}\n", key, value))
+        .collect()
+}
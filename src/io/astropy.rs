@@ -0,0 +1,726 @@
+//! Interchange with [astropy] cosmologies.
+//!
+//! Supports the ECSV table written by `Cosmology.write(..., format="ascii.ecsv")`
+//! and the mapping form produced by `Cosmology.to_format("mapping")`. With
+//! the `serde` feature, the mapping can be read and written as JSON with
+//! quantities stored as `{"value": ..., "unit": ...}`, as in the astropy
+//! documentation's JSON I/O example.
+//!
+//! Neutrinos are treated as massless radiation by [`FLRWCosmology`], so
+//! cosmologies with massive neutrinos keep their masses on a round trip but
+//! have slightly different densities than in astropy.
+//!
+//! [astropy]: <https://docs.astropy.org/en/stable/cosmology/io.html>
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use anyhow::anyhow;
+
+use crate::{
+    cosmology::OmegaFactors, eV, units::PositiveFloat, Background, FLRWCosmology,
+    FloatingPointUnit, Kelvin, KmPerSecPerMpc,
+};
+
+/// Unit string astropy uses for the Hubble constant.
+const H0_UNIT: &str = "km / (Mpc s)";
+
+/// Below this curvature density a cosmology is written as a flat astropy class.
+const FLATNESS_TOLERANCE: f64 = 1e-10;
+
+/// The astropy cosmology classes that can be represented by an [`FLRWCosmology`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AstropyClass {
+    FlatLambdaCDM,
+    LambdaCDM,
+    FlatwCDM,
+    wCDM,
+    Flatw0waCDM,
+    w0waCDM,
+}
+
+impl AstropyClass {
+    /// Whether the class is spatially flat (and so has no `Ode0` parameter).
+    pub fn is_flat(&self) -> bool {
+        matches!(
+            self,
+            AstropyClass::FlatLambdaCDM | AstropyClass::FlatwCDM | AstropyClass::Flatw0waCDM
+        )
+    }
+
+    /// Whether the class has a `w0` parameter.
+    pub fn has_w0(&self) -> bool {
+        !matches!(self, AstropyClass::FlatLambdaCDM | AstropyClass::LambdaCDM)
+    }
+
+    /// Whether the class has a `wa` parameter.
+    pub fn has_wa(&self) -> bool {
+        matches!(self, AstropyClass::Flatw0waCDM | AstropyClass::w0waCDM)
+    }
+
+    /// Names of the parameters of this class in the order astropy uses.
+    pub fn parameters(&self) -> Vec<&'static str> {
+        let mut parameters = vec!["H0", "Om0"];
+        if !self.is_flat() {
+            parameters.push("Ode0");
+        }
+        if self.has_w0() {
+            parameters.push("w0");
+        }
+        if self.has_wa() {
+            parameters.push("wa");
+        }
+        parameters.extend(["Tcmb0", "Neff", "m_nu", "Ob0"]);
+        parameters
+    }
+}
+
+impl fmt::Display for AstropyClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AstropyClass::FlatLambdaCDM => "FlatLambdaCDM",
+            AstropyClass::LambdaCDM => "LambdaCDM",
+            AstropyClass::FlatwCDM => "FlatwCDM",
+            AstropyClass::wCDM => "wCDM",
+            AstropyClass::Flatw0waCDM => "Flatw0waCDM",
+            AstropyClass::w0waCDM => "w0waCDM",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for AstropyClass {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Accept qualified names such as astropy.cosmology.FlatLambdaCDM
+        match s.trim().rsplit('.').next().unwrap_or_default() {
+            "FlatLambdaCDM" => Ok(AstropyClass::FlatLambdaCDM),
+            "LambdaCDM" => Ok(AstropyClass::LambdaCDM),
+            "FlatwCDM" => Ok(AstropyClass::FlatwCDM),
+            "wCDM" => Ok(AstropyClass::wCDM),
+            "Flatw0waCDM" => Ok(AstropyClass::Flatw0waCDM),
+            "w0waCDM" => Ok(AstropyClass::w0waCDM),
+            other => Err(anyhow!("unsupported astropy cosmology class '{}'", other)),
+        }
+    }
+}
+
+/// An astropy cosmology in its mapping form.
+///
+/// Parameters that do not apply to `class` (e.g. `Ode0` for flat classes)
+/// are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AstropyCosmology {
+    pub class: AstropyClass,
+    pub name: Option<String>,
+    pub H0: KmPerSecPerMpc,
+    pub Om0: f64,
+    pub Ode0: Option<f64>,
+    pub w0: Option<f64>,
+    pub wa: Option<f64>,
+    pub Tcmb0: Kelvin,
+    pub Neff: f64,
+    pub m_nu: Vec<eV>,
+    pub Ob0: Option<f64>,
+    /// Metadata, including the literature `reference` if there is one.
+    pub meta: BTreeMap<String, String>,
+}
+
+impl From<&FLRWCosmology> for AstropyCosmology {
+    fn from(cosmology: &FLRWCosmology) -> Self {
+        let flat = cosmology.omega_k0().0.abs() < FLATNESS_TOLERANCE;
+        let dark_energy = cosmology.dark_energy;
+        let class = match (flat, dark_energy.w_a != 0., dark_energy.w_0 != -1.) {
+            (true, true, _) => AstropyClass::Flatw0waCDM,
+            (false, true, _) => AstropyClass::w0waCDM,
+            (true, false, true) => AstropyClass::FlatwCDM,
+            (false, false, true) => AstropyClass::wCDM,
+            (true, false, false) => AstropyClass::FlatLambdaCDM,
+            (false, false, false) => AstropyClass::LambdaCDM,
+        };
+
+        let mut meta = BTreeMap::new();
+        if let Some(reference) = &cosmology.reference {
+            meta.insert("reference".to_string(), reference.clone());
+        }
+
+        Self {
+            class,
+            name: cosmology.name.clone(),
+            H0: cosmology.H_0,
+            Om0: cosmology.omega_m0().0,
+            Ode0: (!class.is_flat()).then(|| cosmology.omega_de0().0),
+            w0: class.has_w0().then_some(dark_energy.w_0),
+            wa: class.has_wa().then_some(dark_energy.w_a),
+            Tcmb0: cosmology.T_CMB0.unwrap_or_default(),
+            Neff: cosmology.N_eff.0,
+            m_nu: cosmology.m_nu.clone(),
+            Ob0: Some(cosmology.omega_b0().0),
+            meta,
+        }
+    }
+}
+
+impl TryFrom<AstropyCosmology> for FLRWCosmology {
+    type Error = anyhow::Error;
+
+    fn try_from(cosmology: AstropyCosmology) -> Result<Self, Self::Error> {
+        let N_eff = PositiveFloat::new(cosmology.Neff)?;
+        let T_CMB0 = (cosmology.Tcmb0 != Kelvin::zero()).then_some(cosmology.Tcmb0.0);
+        // astropy stores a single mass (or none) when all species share it
+        let m_nu = match cosmology.m_nu.len() {
+            0 | 1 => {
+                let mass = cosmology.m_nu.first().copied().unwrap_or(eV::zero());
+                vec![mass; N_eff.floor() as usize]
+            }
+            _ => cosmology.m_nu,
+        };
+        let Ob0 = cosmology.Ob0.unwrap_or(0.);
+        let reference = cosmology.meta.get("reference").cloned();
+
+        let build = |Ode0: f64| {
+            FLRWCosmology::new(
+                cosmology.name.clone(),
+                reference.clone(),
+                cosmology.H0.0,
+                OmegaFactors::new(cosmology.Om0, Ode0, Ob0)?,
+                T_CMB0,
+                Some(N_eff),
+                Some(m_nu.clone()),
+            )
+        };
+        let flrw = match cosmology.Ode0 {
+            Some(Ode0) if !cosmology.class.is_flat() => build(Ode0)?,
+            Some(_) => return Err(anyhow!("Ode0 given for flat class {}", cosmology.class)),
+            None if cosmology.class.is_flat() => {
+                // Dark energy makes up what radiation and matter do not
+                let relativistic = build(1. - cosmology.Om0)?;
+                build(
+                    1. - cosmology.Om0 - relativistic.omega_gamma0().0 - relativistic.omega_nu0().0,
+                )?
+            }
+            None => return Err(anyhow!("missing Ode0 for class {}", cosmology.class)),
+        };
+
+        Ok(flrw.with_dark_energy(cosmology.w0.unwrap_or(-1.), cosmology.wa.unwrap_or(0.)))
+    }
+}
+
+/// Format a list of masses as astropy writes multidimensional columns.
+fn format_masses(m_nu: &[eV]) -> String {
+    let masses: Vec<String> = m_nu.iter().map(|m| format!("{:?}", m.0)).collect();
+    format!("[{}]", masses.join(","))
+}
+
+/// Parse a scalar or list of masses such as `[0.0,0.0,0.06]`.
+fn parse_masses(s: &str) -> Result<Vec<eV>, anyhow::Error> {
+    let s = s.trim();
+    if s.is_empty() || s == "None" || s == "null" {
+        return Ok(vec![]);
+    }
+    let inner = s.trim_start_matches('[').trim_end_matches(']');
+    inner
+        .split(',')
+        .filter(|m| !m.trim().is_empty())
+        .map(|m| m.trim().parse::<eV>())
+        .collect()
+}
+
+/// Remove YAML quotes around a scalar, undoing the doubled single quotes
+/// written by [`quote`].
+fn unquote(s: &str) -> String {
+    let s = s.trim();
+    if s.len() >= 2 && s.starts_with('\'') && s.ends_with('\'') {
+        return s[1..s.len() - 1].replace("''", "'");
+    }
+    if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+        return s[1..s.len() - 1].to_string();
+    }
+    s.to_string()
+}
+
+/// Quote a YAML scalar if it contains characters with special meaning.
+fn quote(s: &str) -> String {
+    if s.contains([':', ',', '{', '}', '[', ']', '#', '\'', '"']) {
+        format!("'{}'", s.replace('\'', "''"))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Split a line of an ECSV table on whitespace, honouring double quotes.
+///
+/// Within a quoted field a doubled double quote `""` stands for `"`.
+fn split_row(line: &str) -> Vec<String> {
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !field.is_empty() || quoted {
+                    fields.push(std::mem::take(&mut field));
+                }
+                quoted = false;
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || quoted {
+        fields.push(field);
+    }
+    fields
+}
+
+/// Quote a field of an ECSV table, doubling embedded double quotes.
+fn quote_field(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+impl AstropyCosmology {
+    /// Serialize as an astropy ECSV table with a single row.
+    pub fn to_ecsv(&self) -> String {
+        let mut header = vec![
+            "%ECSV 1.0".to_string(),
+            "---".to_string(),
+            "datatype:".to_string(),
+            "- {name: name, datatype: string}".to_string(),
+        ];
+        let mut names = vec!["name".to_string()];
+        let mut values = vec![quote_field(self.name.as_deref().unwrap_or_default())];
+
+        for parameter in self.class.parameters() {
+            let (column, value) = match parameter {
+                "H0" => (
+                    format!("{{name: H0, unit: {}, datatype: float64}}", H0_UNIT),
+                    format!("{:?}", self.H0.0),
+                ),
+                "Tcmb0" => (
+                    "{name: Tcmb0, unit: K, datatype: float64}".to_string(),
+                    format!("{:?}", self.Tcmb0.0),
+                ),
+                "m_nu" => (
+                    format!(
+                        "{{name: m_nu, unit: eV, datatype: string, subtype: 'float64[{}]'}}",
+                        self.m_nu.len()
+                    ),
+                    format_masses(&self.m_nu),
+                ),
+                name => {
+                    let value = match name {
+                        "Om0" => Some(self.Om0),
+                        "Ode0" => self.Ode0,
+                        "w0" => self.w0,
+                        "wa" => self.wa,
+                        "Neff" => Some(self.Neff),
+                        _ => self.Ob0,
+                    };
+                    (
+                        format!("{{name: {}, datatype: float64}}", name),
+                        value.map_or("nan".to_string(), |v| format!("{:?}", v)),
+                    )
+                }
+            };
+            header.push(format!("- {}", column));
+            names.push(parameter.to_string());
+            values.push(value);
+        }
+
+        header.push("meta: !!omap".to_string());
+        for (key, value) in &self.meta {
+            header.push(format!("- {{{}: {}}}", key, quote(value)));
+        }
+        header.push(format!("- {{cosmology: {}}}", self.class));
+        header.push("schema: astropy-2.0".to_string());
+
+        let mut ecsv: String = header.iter().map(|line| format!("# {}\n", line)).collect();
+        ecsv.push_str(&names.join(" "));
+        ecsv.push('\n');
+        ecsv.push_str(&values.join(" "));
+        ecsv.push('\n');
+        ecsv
+    }
+
+    /// Parse an astropy ECSV table containing a single cosmology.
+    pub fn from_ecsv(ecsv: &str) -> Result<Self, anyhow::Error> {
+        let mut units = BTreeMap::new();
+        let mut meta = BTreeMap::new();
+        let mut section = "";
+        let mut rows = vec![];
+
+        for line in ecsv.lines() {
+            if let Some(header) = line.strip_prefix('#') {
+                let header = header.trim();
+                if let Some(entry) = header.strip_prefix("- ") {
+                    let entry = entry.trim().trim_start_matches('{').trim_end_matches('}');
+                    match section {
+                        "datatype" => {
+                            let fields: BTreeMap<&str, String> = entry
+                                .split(", ")
+                                .filter_map(|field| field.split_once(": "))
+                                .map(|(key, value)| (key.trim(), unquote(value)))
+                                .collect();
+                            if let (Some(name), Some(unit)) =
+                                (fields.get("name"), fields.get("unit"))
+                            {
+                                units.insert(name.to_string(), unit.to_string());
+                            }
+                        }
+                        "meta" => {
+                            if let Some((key, value)) = entry.split_once(": ") {
+                                meta.insert(key.trim().to_string(), unquote(value));
+                            }
+                        }
+                        _ => {}
+                    }
+                } else if let Some((key, value)) = header.split_once(':') {
+                    if line.starts_with("#   ") {
+                        // Block style metadata, e.g. "#   reference: ..."
+                        if section == "meta" {
+                            meta.insert(key.trim().to_string(), unquote(value));
+                        }
+                    } else {
+                        section = match key.trim() {
+                            "datatype" => "datatype",
+                            "meta" => "meta",
+                            _ => "",
+                        };
+                    }
+                }
+            } else if !line.trim().is_empty() {
+                rows.push(split_row(line));
+            }
+        }
+
+        let (names, values) = match rows.as_slice() {
+            [names, values] => (names, values),
+            [_] => return Err(anyhow!("ECSV table has no rows")),
+            [] => return Err(anyhow!("ECSV table has no columns")),
+            _ => return Err(anyhow!("expected a single cosmology in the ECSV table")),
+        };
+        if names.len() != values.len() {
+            return Err(anyhow!("ECSV row does not match its columns"));
+        }
+        let columns: BTreeMap<&str, &str> = names
+            .iter()
+            .map(|n| n.as_str())
+            .zip(values.iter().map(|v| v.as_str()))
+            .collect();
+
+        let class: AstropyClass = meta
+            .remove("cosmology")
+            .ok_or_else(|| anyhow!("missing cosmology class in ECSV metadata"))?
+            .parse()?;
+        let number = |name: &str| -> Result<Option<f64>, anyhow::Error> {
+            match columns.get(name) {
+                None => Ok(None),
+                Some(v) if v.is_empty() || *v == "nan" || *v == "None" => Ok(None),
+                Some(v) => v
+                    .parse::<f64>()
+                    .map(Some)
+                    .map_err(|e| anyhow!("invalid {} '{}': {}", name, v, e)),
+            }
+        };
+        let quantity = |name: &str| -> Result<String, anyhow::Error> {
+            let value = columns
+                .get(name)
+                .ok_or_else(|| anyhow!("missing {} column", name))?;
+            Ok(format!("{} {}", value, units.get(name).map_or("", |u| u)))
+        };
+
+        Ok(Self {
+            class,
+            name: columns
+                .get("name")
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string()),
+            H0: quantity("H0")?.parse()?,
+            Om0: number("Om0")?.ok_or_else(|| anyhow!("missing Om0 column"))?,
+            Ode0: number("Ode0")?,
+            w0: number("w0")?,
+            wa: number("wa")?,
+            Tcmb0: quantity("Tcmb0")?.parse()?,
+            Neff: number("Neff")?.unwrap_or(0.),
+            m_nu: parse_masses(columns.get("m_nu").copied().unwrap_or_default())?,
+            Ob0: number("Ob0")?,
+            meta,
+        })
+    }
+}
+
+/// Write a cosmology as an astropy ECSV table.
+pub fn write_ecsv(cosmology: &FLRWCosmology) -> String {
+    AstropyCosmology::from(cosmology).to_ecsv()
+}
+
+/// Read a cosmology from an astropy ECSV table.
+pub fn read_ecsv(ecsv: &str) -> Result<FLRWCosmology, anyhow::Error> {
+    AstropyCosmology::from_ecsv(ecsv)?.try_into()
+}
+
+#[cfg(feature = "serde")]
+mod json {
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+
+    use super::*;
+
+    /// A quantity as written by astropy's JSON I/O example.
+    #[derive(Serialize, Deserialize)]
+    struct Quantity {
+        value: Value,
+        unit: String,
+    }
+
+    impl Quantity {
+        fn new(value: Value, unit: &str) -> Self {
+            Self {
+                value,
+                unit: unit.to_string(),
+            }
+        }
+
+        /// Parse a scalar quantity into a unit, converting as needed.
+        fn parse<T: FromStr<Err = anyhow::Error>>(&self) -> Result<T, anyhow::Error> {
+            format!("{} {}", self.value, self.unit).parse()
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct AstropyJson {
+        cosmology: String,
+        name: Option<String>,
+        H0: Quantity,
+        Om0: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        Ode0: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        w0: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        wa: Option<f64>,
+        Tcmb0: Quantity,
+        Neff: f64,
+        m_nu: Option<Quantity>,
+        Ob0: Option<f64>,
+        #[serde(default)]
+        meta: BTreeMap<String, Value>,
+    }
+
+    impl AstropyCosmology {
+        /// Serialize in the JSON form of astropy's mapping representation.
+        pub fn to_json(&self) -> Result<String, anyhow::Error> {
+            let masses = self.m_nu.iter().map(|m| Value::from(m.0)).collect();
+            let meta = self
+                .meta
+                .iter()
+                .map(|(key, value)| {
+                    // Keep numbers and booleans as such
+                    let value =
+                        serde_json::from_str(value).unwrap_or_else(|_| Value::from(value.clone()));
+                    (key.clone(), value)
+                })
+                .collect();
+            let json = AstropyJson {
+                cosmology: self.class.to_string(),
+                name: self.name.clone(),
+                H0: Quantity::new(Value::from(self.H0.0), H0_UNIT),
+                Om0: self.Om0,
+                Ode0: self.Ode0,
+                w0: self.w0,
+                wa: self.wa,
+                Tcmb0: Quantity::new(Value::from(self.Tcmb0.0), "K"),
+                Neff: self.Neff,
+                m_nu: Some(Quantity::new(Value::Array(masses), "eV")),
+                Ob0: self.Ob0,
+                meta,
+            };
+            Ok(serde_json::to_string_pretty(&json)?)
+        }
+
+        /// Parse the JSON form of astropy's mapping representation.
+        pub fn from_json(json: &str) -> Result<Self, anyhow::Error> {
+            let json: AstropyJson = serde_json::from_str(json)?;
+            let m_nu = match json.m_nu {
+                None => vec![],
+                Some(Quantity {
+                    value: Value::Array(masses),
+                    unit,
+                }) => masses
+                    .iter()
+                    .map(|m| Quantity::new(m.clone(), &unit).parse())
+                    .collect::<Result<_, _>>()?,
+                Some(mass) => vec![mass.parse()?],
+            };
+            let meta = json
+                .meta
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Value::String(value) => (key, value),
+                    value => (key, value.to_string()),
+                })
+                .collect();
+
+            Ok(Self {
+                class: json.cosmology.parse()?,
+                name: json.name,
+                H0: json.H0.parse()?,
+                Om0: json.Om0,
+                Ode0: json.Ode0,
+                w0: json.w0,
+                wa: json.wa,
+                Tcmb0: json.Tcmb0.parse()?,
+                Neff: json.Neff,
+                m_nu,
+                Ob0: json.Ob0,
+                meta,
+            })
+        }
+    }
+
+    /// Write a cosmology in astropy's JSON mapping form.
+    pub fn write_json(cosmology: &FLRWCosmology) -> Result<String, anyhow::Error> {
+        AstropyCosmology::from(cosmology).to_json()
+    }
+
+    /// Read a cosmology from astropy's JSON mapping form.
+    pub fn read_json(json: &str) -> Result<FLRWCosmology, anyhow::Error> {
+        AstropyCosmology::from_json(json)?.try_into()
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use json::{read_json, write_json};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Distances, Redshift};
+
+    /// Planck18 as written by astropy 5.1.
+    const PLANCK18_ECSV: &str = r#"# %ECSV 1.0
+# ---
+# datatype:
+# - {name: name, datatype: string}
+# - {name: H0, unit: km / (Mpc s), datatype: float64}
+# - {name: Om0, datatype: float64}
+# - {name: Tcmb0, unit: K, datatype: float64}
+# - {name: Neff, datatype: float64}
+# - {name: m_nu, unit: eV, datatype: string, subtype: 'float64[3]'}
+# - {name: Ob0, datatype: float64}
+# meta: !!omap
+# - {Oc0: 0.2607}
+# - {n: 0.9665}
+# - {sigma8: 0.8102}
+# - {tau: 0.0561}
+# - {z_reion: 7.82}
+# - {t0: 13.787}
+# - {flat: true}
+# - {reference: 'Planck Collaboration 2018, 2020, A&A, 641, A6  (Paper VI), Table 2 (TT, TE, EE + lowE + lensing + BAO)'}
+# - {cosmology: FlatLambdaCDM}
+# schema: astropy-2.0
+name H0 Om0 Tcmb0 Neff m_nu Ob0
+Planck18 67.66 0.30966 2.7255 3.046 "[0.0, 0.0, 0.06]" 0.04897
+"#;
+
+    #[test]
+    fn read_astropy_planck18() {
+        let cosmology = read_ecsv(PLANCK18_ECSV).unwrap();
+        assert_eq!(cosmology.name.as_deref(), Some("Planck18"));
+        assert!(cosmology
+            .reference
+            .as_deref()
+            .unwrap()
+            .starts_with("Planck"));
+        assert_eq!(cosmology.H_0, KmPerSecPerMpc::new(67.66));
+        assert_eq!(cosmology.m_nu[2], eV::new(0.06));
+        assert!(cosmology.omega_k0().0.abs() < 1e-12);
+        assert!(cosmology.dark_energy.is_cosmological_constant());
+    }
+
+    #[test]
+    fn ecsv_round_trip() {
+        let omegas = OmegaFactors::new(0.3, 0.65, 0.05).unwrap();
+        let cosmology = FLRWCosmology::new(
+            Some("open w0wa".to_string()),
+            Some("made up: for testing".to_string()),
+            70.,
+            omegas,
+            Some(2.7255),
+            None,
+            None,
+        )
+        .unwrap()
+        .with_dark_energy(-0.9, 0.1);
+
+        let astropy = AstropyCosmology::from(&cosmology);
+        assert_eq!(astropy.class, AstropyClass::w0waCDM);
+
+        let ecsv = write_ecsv(&cosmology);
+        assert_eq!(AstropyCosmology::from_ecsv(&ecsv).unwrap(), astropy);
+        let loaded = read_ecsv(&ecsv).unwrap();
+        assert_eq!(loaded, cosmology);
+    }
+
+    #[test]
+    fn quotes_round_trip() {
+        let omegas = OmegaFactors::new(0.3, 0.7, 0.05).unwrap();
+        let cosmology = FLRWCosmology::new(
+            Some("My \"best\" fit".to_string()),
+            Some("Riess et al.'s fit".to_string()),
+            70.,
+            omegas,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let loaded = read_ecsv(&write_ecsv(&cosmology)).unwrap();
+        assert_eq!(loaded.name.as_deref(), Some("My \"best\" fit"));
+        assert_eq!(loaded.reference.as_deref(), Some("Riess et al.'s fit"));
+        assert_eq!(unquote("'it''s'"), "it's");
+        assert_eq!(split_row(r#"a "b ""c""" d"#), ["a", "b \"c\"", "d"]);
+    }
+
+    #[test]
+    fn flat_classes_round_trip() {
+        let cosmology = read_ecsv(PLANCK18_ECSV).unwrap();
+        let loaded = read_ecsv(&write_ecsv(&cosmology)).unwrap();
+        let z = Redshift::new(1.0);
+        assert!(
+            (loaded.luminosity_distance(z).0 - cosmology.luminosity_distance(z).0).abs() < 1e-6
+        );
+        assert_eq!(
+            AstropyCosmology::from(&loaded).class,
+            AstropyClass::FlatLambdaCDM
+        );
+    }
+
+    #[test]
+    fn unsupported_class() {
+        let ecsv = PLANCK18_ECSV.replace("FlatLambdaCDM}", "Flatw0wzCDM}");
+        assert!(read_ecsv(&ecsv).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let cosmology = read_ecsv(PLANCK18_ECSV).unwrap();
+        let json = write_json(&cosmology).unwrap();
+        assert!(json.contains(r#""cosmology": "FlatLambdaCDM""#));
+        assert!(json.contains(r#""unit": "km / (Mpc s)""#));
+
+        let astropy = AstropyCosmology::from_json(&json).unwrap();
+        assert_eq!(astropy, AstropyCosmology::from(&cosmology));
+        assert_eq!(read_json(&json).unwrap().H_0, cosmology.H_0);
+    }
+}
@@ -0,0 +1,225 @@
+//! [CAMB] parameter files.
+//!
+//! Only the parameters that determine the background expansion are
+//! written and read. `Omega_M0` is taken to include massive neutrinos, whose
+//! physical density $\sum m_\nu / 93.14$ eV is written to `omnuh2` and
+//! taken out of `omch2`. CAMB then closes the budget with the same dark
+//! energy density, and the model stays flat if it was.
+//!
+//! [CAMB]: <https://camb.info/readme.html>
+use crate::{
+    cosmology::OmegaFactors,
+    eV,
+    io::{format_ini, massive_neutrino_density, IniParameters, NEUTRINO_MASS_PER_OMEGA_NU_H2},
+    units::PositiveFloat,
+    Background, FLRWCosmology, FloatingPointUnit,
+};
+
+/// CAMB's default CMB temperature in Kelvin.
+const DEFAULT_TEMP_CMB: f64 = 2.7255;
+
+/// CAMB's default effective number of neutrino species.
+const DEFAULT_N_EFF: f64 = 3.044;
+
+/// Write the background parameters of a cosmology as a CAMB `.ini` file.
+pub fn write_ini(cosmology: &FLRWCosmology) -> String {
+    let h2 = cosmology.little_h().powf(2.);
+    let massive: Vec<f64> = cosmology
+        .m_nu
+        .iter()
+        .map(|m| m.0)
+        .filter(|m| *m > 0.)
+        .collect();
+    let total_mass: f64 = massive.iter().sum();
+    let omega_nu_h2 = massive_neutrino_density(&cosmology.m_nu);
+    let fractions: Vec<String> = massive
+        .iter()
+        .map(|m| format!("{}", m / total_mass))
+        .collect();
+
+    let mut parameters = vec![
+        ("use_physical", "T".to_string()),
+        ("hubble", format!("{}", cosmology.H_0.0)),
+        ("ombh2", format!("{}", cosmology.omega_b0().0 * h2)),
+        (
+            "omch2",
+            format!("{}", cosmology.omega_dm0().0 * h2 - omega_nu_h2),
+        ),
+        ("omnuh2", format!("{}", omega_nu_h2)),
+        ("omk", format!("{}", cosmology.omega_k0().0)),
+        (
+            "temp_cmb",
+            format!("{}", cosmology.T_CMB0.unwrap_or_default().0),
+        ),
+        (
+            "massless_neutrinos",
+            format!("{}", cosmology.N_eff.0 - massive.len() as f64),
+        ),
+        ("massive_neutrinos", format!("{}", massive.len())),
+        ("nu_mass_eigenstates", format!("{}", massive.len())),
+        ("share_delta_neff", "F".to_string()),
+    ];
+    if !massive.is_empty() {
+        parameters.push(("nu_mass_degeneracies", vec!["1"; massive.len()].join(" ")));
+        parameters.push(("nu_mass_fractions", fractions.join(" ")));
+    }
+    parameters.extend([
+        ("dark_energy_model", "ppf".to_string()),
+        ("w", format!("{}", cosmology.dark_energy.w_0)),
+        ("wa", format!("{}", cosmology.dark_energy.w_a)),
+    ]);
+    format_ini(&parameters)
+}
+
+/// Read the background parameters of a CAMB `.ini` file.
+pub fn read_ini(ini: &str) -> Result<FLRWCosmology, anyhow::Error> {
+    let parameters = IniParameters::parse(ini);
+    let H_0 = parameters.required(&["hubble"])?;
+    let h2 = (H_0 / 100.).powi(2);
+
+    let physical = parameters.boolean("use_physical").unwrap_or(true);
+    let (omega_b, omega_c, omega_nu) = if physical {
+        (
+            parameters.required(&["ombh2"])? / h2,
+            parameters.required(&["omch2"])? / h2,
+            parameters.number(&["omnuh2"])?.unwrap_or(0.) / h2,
+        )
+    } else {
+        (
+            parameters.required(&["omega_baryon"])?,
+            parameters.required(&["omega_cdm"])?,
+            parameters.number(&["omega_neutrino"])?.unwrap_or(0.),
+        )
+    };
+    let omega_k = parameters.number(&["omk"])?.unwrap_or(0.);
+    let T_CMB0 = parameters
+        .number(&["temp_cmb"])?
+        .unwrap_or(DEFAULT_TEMP_CMB);
+
+    let massive = parameters.number(&["massive_neutrinos"])?.unwrap_or(0.);
+    let massless = parameters
+        .number(&["massless_neutrinos"])?
+        .unwrap_or(DEFAULT_N_EFF - massive);
+    let N_eff = PositiveFloat::new(massless + massive)?;
+
+    let total_mass = omega_nu * h2 * NEUTRINO_MASS_PER_OMEGA_NU_H2;
+    let mut fractions = parameters.numbers("nu_mass_fractions")?;
+    if fractions.is_empty() && massive > 0. {
+        fractions = vec![1. / massive; massive as usize];
+    }
+    let mut m_nu: Vec<eV> = fractions.iter().map(|f| eV::new(f * total_mass)).collect();
+    // Remaining species are massless
+    while (m_nu.len() as f64) < N_eff.floor() {
+        m_nu.insert(0, eV::zero());
+    }
+
+    // Dark energy makes up the remainder of the energy budget, with massive
+    // neutrinos counted as matter
+    let omega_m = omega_b + omega_c + omega_nu;
+    let without_dark_energy = FLRWCosmology::new(
+        None,
+        None,
+        H_0,
+        OmegaFactors::new(omega_m, 0., omega_b)?,
+        Some(T_CMB0),
+        Some(N_eff),
+        Some(m_nu.clone()),
+    )?;
+    let omega_de = without_dark_energy.omega_k0().0 - omega_k;
+
+    Ok(FLRWCosmology::new(
+        None,
+        None,
+        H_0,
+        OmegaFactors::new(omega_m, omega_de, omega_b)?,
+        Some(T_CMB0),
+        Some(N_eff),
+        Some(m_nu),
+    )?
+    .with_dark_energy(
+        parameters.number(&["w"])?.unwrap_or(-1.),
+        parameters.number(&["wa"])?.unwrap_or(0.),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let omegas = OmegaFactors::new(0.31, 0.68, 0.049).unwrap();
+        let cosmology = FLRWCosmology::new(
+            None,
+            None,
+            67.7,
+            omegas,
+            Some(2.7255),
+            Some(PositiveFloat(3.046)),
+            Some(vec![eV::zero(), eV::zero(), eV::new(0.06)]),
+        )
+        .unwrap()
+        .with_dark_energy(-0.95, 0.1);
+
+        let ini = write_ini(&cosmology);
+        assert!(ini.contains("massless_neutrinos = 2.046"));
+        assert!(ini.contains("massive_neutrinos = 1\n"));
+
+        let loaded = read_ini(&ini).unwrap();
+        assert!((loaded.omega_m0().0 - 0.31).abs() < 1e-12);
+        assert!((loaded.omega_b0().0 - 0.049).abs() < 1e-12);
+        assert!((loaded.omega_de0().0 - 0.68).abs() < 1e-12);
+        assert!((loaded.omega_k0().0 - cosmology.omega_k0().0).abs() < 1e-12);
+        assert!((loaded.N_eff.0 - 3.046).abs() < 1e-12);
+        assert!((loaded.m_nu[2].0 - 0.06).abs() < 1e-12);
+        assert_eq!(loaded.dark_energy, cosmology.dark_energy);
+    }
+
+    #[test]
+    fn massive_neutrinos_close_the_budget() {
+        let omegas = OmegaFactors::new(0.31, 0.69, 0.049).unwrap();
+        let m_nu = vec![eV::zero(), eV::zero(), eV::new(0.06)];
+        let cosmology =
+            FLRWCosmology::new(None, None, 67.7, omegas, None, None, Some(m_nu)).unwrap();
+        let h2 = 0.677_f64.powi(2);
+
+        // CAMB's dark energy, 1 - (ombh2 + omch2 + omnuh2) / h^2 - omk, is the same
+        let parameters = IniParameters::parse(&write_ini(&cosmology));
+        let omega_nu_h2 = parameters.required(&["omnuh2"]).unwrap();
+        assert!((omega_nu_h2 - 0.06 / NEUTRINO_MASS_PER_OMEGA_NU_H2).abs() < 1e-15);
+        let matter_h2 = ["ombh2", "omch2", "omnuh2"]
+            .iter()
+            .map(|key| parameters.required(&[key]).unwrap())
+            .sum::<f64>();
+        let omk = parameters.required(&["omk"]).unwrap();
+        assert!((1. - matter_h2 / h2 - omk - 0.69).abs() < 1e-12);
+
+        let loaded = read_ini(&write_ini(&cosmology)).unwrap();
+        assert!((loaded.omega_m0().0 - 0.31).abs() < 1e-12);
+        assert!(loaded.omega_k0().0.abs() < 1e-12);
+    }
+
+    #[test]
+    fn physical_densities() {
+        let ini = "
+            # Planck 2018-like
+            use_physical = T
+            ombh2 = 0.0224
+            omch2 = 0.12
+            omk = 0
+            hubble = 67.4
+            massless_neutrinos = 3.044
+        ";
+        let cosmology = read_ini(ini).unwrap();
+        assert!((cosmology.omega_b0().0 - 0.0224 / 0.674_f64.powi(2)).abs() < 1e-12);
+        assert!((cosmology.omega_m0().0 - 0.1424 / 0.674_f64.powi(2)).abs() < 1e-12);
+
+        // Massive neutrinos count as matter
+        let with_neutrinos = read_ini(&format!("{}\nomnuh2 = 0.000644", ini)).unwrap();
+        let omega_m = (0.1424 + 0.000644) / 0.674_f64.powi(2);
+        assert!((with_neutrinos.omega_m0().0 - omega_m).abs() < 1e-12);
+        assert!(with_neutrinos.omega_k0().0.abs() < 1e-12);
+        assert!(cosmology.omega_k0().0.abs() < 1e-12);
+        assert!(read_ini("ombh2 = 0.0224").is_err());
+    }
+}
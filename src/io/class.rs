@@ -0,0 +1,274 @@
+//! [CLASS] parameter files.
+//!
+//! Only the parameters that determine the background expansion are
+//! written and read. The background table that CLASS writes
+//! (`background.dat`) can be read as a [`TabulatedCosmology`].
+//!
+//! Massive neutrinos are written as `ncdm` species with the default CLASS
+//! temperature, each contributing 1.0132 to $N_{eff}$ at early times, and
+//! the remainder of $N_{eff}$ is written to `N_ur`. `Omega_M0` is taken to
+//! include them, so their density $\sum m_\nu / 93.14$ eV is taken out of
+//! `omega_cdm` and CLASS closes the budget with the same dark energy density.
+//!
+//! [CLASS]: <https://github.com/lesgourg/class_public/blob/master/explanatory.ini>
+use anyhow::anyhow;
+
+use crate::{
+    constants::C_M_PER_S,
+    cosmology::{read_columns, OmegaFactors, TabulatedCosmology},
+    eV,
+    io::{format_ini, massive_neutrino_density, IniParameters},
+    units::PositiveFloat,
+    Background, FLRWCosmology, FloatingPointUnit, KmPerSecPerMpc, Redshift,
+};
+
+/// Contribution to $N_{eff}$ of each massive neutrino species in CLASS.
+const N_EFF_PER_NCDM: f64 = 1.0132;
+
+/// CLASS's default CMB temperature in Kelvin.
+const DEFAULT_T_CMB: f64 = 2.7255;
+
+/// CLASS's default number of ultra-relativistic species.
+const DEFAULT_N_UR: f64 = 3.044;
+
+/// Write the background parameters of a cosmology as a CLASS `.ini` file.
+pub fn write_ini(cosmology: &FLRWCosmology) -> String {
+    let h2 = cosmology.little_h().powf(2.);
+    let massive: Vec<String> = cosmology
+        .m_nu
+        .iter()
+        .filter(|m| m.0 > 0.)
+        .map(|m| format!("{}", m.0))
+        .collect();
+
+    let mut parameters = vec![
+        ("h", format!("{}", cosmology.little_h().0)),
+        ("omega_b", format!("{}", cosmology.omega_b0().0 * h2)),
+        (
+            "omega_cdm",
+            format!(
+                "{}",
+                cosmology.omega_dm0().0 * h2 - massive_neutrino_density(&cosmology.m_nu)
+            ),
+        ),
+        ("Omega_k", format!("{}", cosmology.omega_k0().0)),
+        (
+            "T_cmb",
+            format!("{}", cosmology.T_CMB0.unwrap_or_default().0),
+        ),
+        (
+            "N_ur",
+            format!(
+                "{}",
+                cosmology.N_eff.0 - N_EFF_PER_NCDM * massive.len() as f64
+            ),
+        ),
+        ("N_ncdm", format!("{}", massive.len())),
+    ];
+    if !massive.is_empty() {
+        parameters.push(("m_ncdm", massive.join(", ")));
+    }
+    if !cosmology.dark_energy.is_cosmological_constant() {
+        // The fluid takes the place of the cosmological constant
+        parameters.extend([
+            ("Omega_Lambda", "0".to_string()),
+            ("w0_fld", format!("{}", cosmology.dark_energy.w_0)),
+            ("wa_fld", format!("{}", cosmology.dark_energy.w_a)),
+        ]);
+    }
+    format_ini(&parameters)
+}
+
+/// Read the background parameters of a CLASS `.ini` file.
+pub fn read_ini(ini: &str) -> Result<FLRWCosmology, anyhow::Error> {
+    let parameters = IniParameters::parse(ini);
+    let h = match parameters.number(&["h"])? {
+        Some(h) => h,
+        None => parameters.required(&["H0"])? / 100.,
+    };
+    let h2 = h.powi(2);
+
+    let omega_b = match parameters.number(&["omega_b"])? {
+        Some(omega_b) => omega_b / h2,
+        None => parameters.required(&["Omega_b"])?,
+    };
+    let omega_c = match parameters.number(&["omega_cdm"])? {
+        Some(omega_c) => omega_c / h2,
+        None => parameters.required(&["Omega_cdm"])?,
+    };
+    let omega_k = parameters.number(&["Omega_k"])?.unwrap_or(0.);
+    let T_CMB0 = parameters.number(&["T_cmb"])?.unwrap_or(DEFAULT_T_CMB);
+
+    let masses = parameters.numbers("m_ncdm")?;
+    let N_ncdm = parameters
+        .number(&["N_ncdm"])?
+        .unwrap_or(masses.len() as f64);
+    let N_ur = parameters.number(&["N_ur"])?.unwrap_or(DEFAULT_N_UR);
+    let N_eff = PositiveFloat::new(N_ur + N_EFF_PER_NCDM * N_ncdm)?;
+
+    let mut m_nu: Vec<eV> = masses.into_iter().map(eV::new).collect();
+    // Remaining species are massless
+    while (m_nu.len() as f64) < N_eff.floor() {
+        m_nu.insert(0, eV::zero());
+    }
+
+    // Dark energy makes up the remainder of the energy budget, with massive
+    // neutrinos counted as matter
+    let omega_m = omega_b + omega_c + massive_neutrino_density(&m_nu) / h2;
+    let without_dark_energy = FLRWCosmology::new(
+        None,
+        None,
+        100. * h,
+        OmegaFactors::new(omega_m, 0., omega_b)?,
+        Some(T_CMB0),
+        Some(N_eff),
+        Some(m_nu.clone()),
+    )?;
+    let omega_de = without_dark_energy.omega_k0().0 - omega_k;
+
+    Ok(FLRWCosmology::new(
+        None,
+        None,
+        100. * h,
+        OmegaFactors::new(omega_m, omega_de, omega_b)?,
+        Some(T_CMB0),
+        Some(N_eff),
+        Some(m_nu),
+    )?
+    .with_dark_energy(
+        parameters.number(&["w0_fld"])?.unwrap_or(-1.),
+        parameters.number(&["wa_fld"])?.unwrap_or(0.),
+    ))
+}
+
+/// Read the expansion history from a CLASS `background.dat` table.
+///
+/// The columns are found by name from the header (`z` and `H [1/Mpc]`),
+/// falling back to the first and fourth columns in older CLASS versions.
+pub fn read_background(table: &str) -> Result<TabulatedCosmology, anyhow::Error> {
+    let header = table
+        .lines()
+        .filter(|line| line.starts_with('#'))
+        .find(|line| line.contains("H [1/Mpc]"))
+        .map(column_names)
+        .unwrap_or_default();
+    let column = |name: &str, default: usize| {
+        header
+            .iter()
+            .position(|column| column == name)
+            .unwrap_or(default)
+    };
+
+    let (z, H) = read_columns(table, column("z", 0), column("H [1/Mpc]", 3))?;
+    if z.is_empty() {
+        return Err(anyhow!("no rows in CLASS background table"));
+    }
+    // CLASS tabulates H / c
+    let c_km_per_s = C_M_PER_S / 1000.;
+    TabulatedCosmology::new(
+        &z.into_iter().map(Redshift::new).collect::<Vec<_>>(),
+        &H.into_iter()
+            .map(|H| KmPerSecPerMpc::new(H * c_km_per_s))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Column names of a CLASS header such as `# 1:z  2:proper time [Gyr]  ...`.
+fn column_names(header: &str) -> Vec<String> {
+    let header = header.trim_start_matches('#');
+    let mut names: Vec<String> = vec![];
+    let mut name = String::new();
+    let mut tokens = header.split_whitespace().peekable();
+    while let Some(token) = tokens.next() {
+        match token.split_once(':') {
+            Some((number, rest)) if number.parse::<usize>().is_ok() => {
+                if !name.is_empty() {
+                    names.push(name.trim().to_string());
+                }
+                name = rest.to_string();
+            }
+            _ => {
+                name.push(' ');
+                name.push_str(token);
+            }
+        }
+        if tokens.peek().is_none() && !name.is_empty() {
+            names.push(name.trim().to_string());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let omegas = OmegaFactors::new(0.31, 0.68, 0.049).unwrap();
+        let cosmology = FLRWCosmology::new(
+            None,
+            None,
+            67.7,
+            omegas,
+            Some(2.7255),
+            Some(PositiveFloat(3.046)),
+            Some(vec![eV::zero(), eV::zero(), eV::new(0.06)]),
+        )
+        .unwrap();
+
+        let ini = write_ini(&cosmology);
+        assert!(ini.contains("N_ncdm = 1\n"));
+        assert!(ini.contains("m_ncdm = 0.06\n"));
+        let omega_cdm = IniParameters::parse(&ini).required(&["omega_cdm"]).unwrap();
+        let expected = (0.31 - 0.049) * 0.677_f64.powi(2) - 0.06 / 93.14;
+        assert!((omega_cdm - expected).abs() < 1e-12);
+        assert!(!ini.contains("w0_fld"));
+
+        let loaded = read_ini(&ini).unwrap();
+        assert!((loaded.H_0.0 - 67.7).abs() < 1e-12);
+        assert!((loaded.omega_m0().0 - 0.31).abs() < 1e-12);
+        assert!((loaded.omega_de0().0 - 0.68).abs() < 1e-12);
+        assert!((loaded.N_eff.0 - 3.046).abs() < 1e-12);
+        assert!((loaded.omega_k0().0 - cosmology.omega_k0().0).abs() < 1e-12);
+        assert_eq!(loaded.m_nu.len(), 3);
+        assert!(loaded.dark_energy.is_cosmological_constant());
+    }
+
+    #[test]
+    fn dark_energy_fluid() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.).with_dark_energy(-0.9, 0.);
+        let ini = write_ini(&cosmology);
+        assert!(ini.contains("Omega_Lambda = 0\n"));
+        assert!(ini.contains("w0_fld = -0.9\n"));
+
+        let loaded = read_ini(&ini).unwrap();
+        assert_eq!(loaded.dark_energy, cosmology.dark_energy);
+        assert!((loaded.omega_de0().0 - 0.7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn background_table() {
+        let table = "\
+# Table of selected background quantitites
+# All densities are multiplied by (8piG/3) (below, shortcut notation (.) for this factor)
+#        1:z            2:proper time [Gyr]     3:conf. time [Mpc]           4:H [1/Mpc]
+      3.000000000000e+00      2.150000000000e+00      1.000000000000e+04      1.050000000000e-03
+      2.000000000000e+00      3.300000000000e+00      1.100000000000e+04      6.940000000000e-04
+      1.000000000000e+00      5.900000000000e+00      1.200000000000e+04      4.100000000000e-04
+      0.000000000000e+00      1.380000000000e+01      1.400000000000e+04      2.258000000000e-04
+";
+        let cosmology = read_background(table).unwrap();
+        assert!((cosmology.H_0.0 - 2.258e-4 * 299792.458).abs() < 1e-9);
+        assert!((cosmology.z_max().0 - 3.).abs() < 1e-12);
+        assert!(read_background("# 1:z 4:H [1/Mpc]\n").is_err());
+    }
+
+    #[test]
+    fn header_columns() {
+        assert_eq!(
+            column_names("#  1:z  2:proper time [Gyr]  3:H [1/Mpc]"),
+            vec!["z", "proper time [Gyr]", "H [1/Mpc]"]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! K-corrections following [Hogg et al. 2002].
+//!
+//! The K-correction $K$ relates the apparent magnitude $m$ in an observed
+//! band to the absolute magnitude $M$ in a rest-frame band:
+//! $m = M + \mu(z) + K$, where $\mu$ is the distance modulus. Equivalently a
+//! band luminosity is $L = 4 \pi d_L^2 F \times 10^{0.4 K}$, see
+//! [`luminosity_factor`] and [`crate::Distances::band_luminosity`].
+//!
+//! [Hogg et al. 2002]: <https://arxiv.org/abs/astro-ph/0210394>
+use crate::{
+    photometry::{Filter, Sed},
+    units::PositiveFloat,
+    DimensionlessFloat, DimensionlessPositiveFloat, FloatingPointUnit, Redshift,
+};
+
+/// K-correction in magnitudes of a power-law spectrum $f_\nu \propto \nu^\alpha$,
+/// $K = -2.5 (1 + \alpha) \log_{10}(1+z)$.
+///
+/// This holds for any band, and for band-integrated fluxes as well as
+/// magnitudes. Radio spectral indices are often defined with the opposite
+/// sign, $S_\nu \propto \nu^{-\alpha}$.
+pub fn power_law(alpha: f64, z: Redshift) -> DimensionlessFloat {
+    DimensionlessFloat::new(-2.5 * (1. + alpha) * (1. + z.0).log10())
+}
+
+/// K-correction in magnitudes of an X-ray spectrum with photon index $\Gamma$,
+/// i.e. photon flux $N(E) \propto E^{-\Gamma}$.
+///
+/// The corresponding [`luminosity_factor`] is $(1+z)^{\Gamma - 2}$.
+pub fn x_ray(photon_index: f64, z: Redshift) -> DimensionlessFloat {
+    power_law(1. - photon_index, z)
+}
+
+/// K-correction in magnitudes of the rest-frame spectrum `sed` at redshift
+/// `z`, observed and reported in the same band `filter`.
+///
+/// The filter at wavelengths $\lambda$ and $\lambda / (1+z)$ must be covered by the SED.
+pub fn from_sed(
+    sed: &Sed,
+    filter: &Filter,
+    z: Redshift,
+) -> Result<DimensionlessFloat, anyhow::Error> {
+    from_sed_between_bands(sed, filter, filter, z)
+}
+
+/// K-correction in magnitudes of the rest-frame spectrum `sed` at redshift
+/// `z`, from the rest-frame band `rest_frame` to the observed band `observed`.
+///
+/// Magnitudes in both bands are AB magnitudes.
+pub fn from_sed_between_bands(
+    sed: &Sed,
+    rest_frame: &Filter,
+    observed: &Filter,
+    z: Redshift,
+) -> Result<DimensionlessFloat, anyhow::Error> {
+    let observed_luminosity = sed.band_integral(observed, 1. + z.0)? / (1. + z.0);
+    let rest_frame_luminosity = sed.band_integral(rest_frame, 1.)?;
+
+    // Photon counts of the AB standard (constant f_nu) in each band
+    let ab_rest_frame = rest_frame.integrate(|wavelength| 1. / wavelength, []);
+    let ab_observed = observed.integrate(|wavelength| 1. / wavelength, []);
+
+    Ok(DimensionlessFloat::new(
+        -2.5 * (observed_luminosity / rest_frame_luminosity * ab_rest_frame / ab_observed).log10(),
+    ))
+}
+
+/// Factor $10^{0.4 K}$ converting $4 \pi d_L^2 F$ for a flux $F$ observed in
+/// a band into the luminosity in the corresponding rest-frame band.
+pub fn luminosity_factor(k_correction: DimensionlessFloat) -> DimensionlessPositiveFloat {
+    PositiveFloat(10_f64.powf(0.4 * k_correction.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::photometry::test_helpers::{power_law_sed, top_hat};
+
+    #[test]
+    fn analytic_power_laws() {
+        let z = Redshift::new(1.);
+        // A flat f_nu spectrum has K = -2.5 log10(1+z)
+        assert!((power_law(0., z).0 + 2.5 * 2_f64.log10()).abs() < 1e-12);
+        assert_eq!(power_law(-1., z).0, 0.);
+        assert_eq!(power_law(0.7, Redshift::zero()).0, 0.);
+
+        let factor = luminosity_factor(x_ray(1.7, z));
+        assert!((factor.0 - 2_f64.powf(1.7 - 2.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn numerical_matches_analytic() {
+        let filter = top_hat(5000., 6000.);
+        for alpha in [-2., -0.5, 0., 1.] {
+            let sed = power_law_sed(alpha, 1.);
+            for z in [0., 0.5, 2.] {
+                let z = Redshift::new(z);
+                let k = from_sed(&sed, &filter, z).unwrap();
+                assert!((k.0 - power_law(alpha, z).0).abs() < 1e-4);
+            }
+        }
+
+        // Across bands a flat f_nu spectrum needs only the bandpass correction
+        let sed = power_law_sed(0., 1.);
+        let z = Redshift::new(1.);
+        let k = from_sed_between_bands(&sed, &top_hat(2500., 3000.), &filter, z).unwrap();
+        assert!((k.0 - power_law(0., z).0).abs() < 1e-4);
+
+        assert!(from_sed(&sed, &filter, Redshift::new(20.)).is_err());
+    }
+}
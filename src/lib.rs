@@ -1,25 +1,48 @@
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
+pub mod background;
 pub mod constants;
 pub mod cosmology;
 pub mod dark_energy;
 pub mod distances;
+pub mod igm;
+pub(crate) mod integrate;
+pub(crate) mod interpolate;
+pub mod inverse;
+pub mod io;
+pub mod kcorrection;
+pub mod photometry;
 pub mod redshift;
+pub mod redshift_effects;
 pub mod units;
 
-pub use cosmology::FLRWCosmology;
-pub use distances::Distances;
+pub use background::Background;
+pub use cosmology::{FLRWCosmology, TabulatedCosmology};
+pub use distances::{CurvatureRegion, Distances, LittleHDistances};
 
 // Common units are re-exported from the crate root for convenience.
 pub use redshift::Redshift;
+#[allow(deprecated)]
+pub use units::KilogramsPerMeter3;
 pub use units::{
+    angle::{Arcminute, Arcsecond, Degree, Radian, SquareDegree, Steradian},
+    density::{GramPerCm3, KilogramPerMeter3, MsunPerMpc3},
     energy::{eV, Joule},
-    length::{Kilometer, Meter, Mpc},
-    mass::{Gram, Kilogram},
+    flux::{
+        ErgPerSecPerCm2, ErgPerSecPerCm2PerAngstrom, ErgPerSecPerCm2PerHz, Jansky, WattPerMeter2,
+        WattPerMeter2PerHz,
+    },
+    frequency::Hertz,
+    length::{Angstrom, Kilometer, Kpc, Meter, Micrometer, Mpc, Mpc3, Nanometer},
+    luminosity::{
+        ErgPerSecPerAngstrom, ErgPerSecPerHz, ErgPerSecond, SolarLuminosity, Watt, WattPerHz,
+    },
+    magnitude::{ABMagnitude, Magnitude, VegaMagnitude},
+    mass::{Gram, Kilogram, SolarMass},
     temperature::Kelvin,
     time::{Gyr, Seconds},
-    DimensionlessFloat, DimensionlessPositiveFloat, HInvKmPerSecPerMpc, KilogramsPerMeter3,
-    KmPerSecPerMpc,
+    DimensionlessFloat, DimensionlessPositiveFloat, H2MsunPerMpc3, HInvKmPerSecPerMpc, HInvMpc,
+    HInvMpc3, HInvSolarMass, KmPerSecPerMpc,
 };
 
 // Common traits are re-exported from the crate root also.
@@ -0,0 +1,583 @@
+//! Synthetic photometry: filter transmission curves, spectral energy
+//! distributions, and the magnitudes and colors of redshifted spectra.
+//!
+//! Wavelengths are in Angstroms throughout. Band averages weight by the
+//! number of photons, i.e. by $\lambda R(\lambda)$, as appropriate for
+//! photon-counting detectors.
+use anyhow::anyhow;
+
+use crate::{
+    constants::C_M_PER_S, cosmology::read_columns, distances::luminosity_area, igm::IgmModel,
+    interpolate::LinearInterpolation, units::length::ANGSTROM_TO_METER, ABMagnitude, Angstrom,
+    DimensionlessFloat, Distances, ErgPerSecPerAngstrom, ErgPerSecPerCm2PerAngstrom,
+    ErgPerSecPerCm2PerHz, FloatingPointUnit, Jansky, Redshift, VegaMagnitude,
+};
+
+/// A filter transmission curve $R(\lambda)$.
+///
+/// The throughput is interpolated linearly and is zero outside the
+/// tabulated wavelengths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Filter {
+    /// A descriptive name.
+    pub name: Option<String>,
+
+    /// Throughput as a function of wavelength in Angstroms.
+    throughput: LinearInterpolation,
+}
+
+impl Filter {
+    /// Instantiate a filter from its `throughput` at increasing `wavelength`s.
+    pub fn new(wavelength: &[Angstrom], throughput: &[f64]) -> Result<Self, anyhow::Error> {
+        if wavelength.first().is_some_and(|w| w.0 <= 0.) {
+            return Err(anyhow!("filter wavelengths must be positive"));
+        }
+        if throughput.iter().any(|r| *r < 0.) || throughput.iter().all(|r| *r == 0.) {
+            return Err(anyhow!(
+                "filter throughput must be non-negative and somewhere positive"
+            ));
+        }
+        let throughput = LinearInterpolation::new(
+            wavelength.iter().map(|w| w.0).collect(),
+            throughput.to_vec(),
+        )?;
+        Ok(Self {
+            name: None,
+            throughput,
+        })
+    }
+
+    /// Set a descriptive name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Shortest and longest tabulated wavelengths.
+    pub fn wavelength_range(&self) -> (Angstrom, Angstrom) {
+        let wavelength = self.throughput.x();
+        (
+            Angstrom::new(wavelength[0]),
+            Angstrom::new(wavelength[wavelength.len() - 1]),
+        )
+    }
+
+    /// Throughput at wavelength `wavelength`.
+    pub fn transmission(&self, wavelength: Angstrom) -> f64 {
+        self.throughput.eval(wavelength.0).unwrap_or(0.)
+    }
+
+    /// Read a whitespace separated table with wavelength in Angstroms in
+    /// the first column and throughput in the second, as distributed by
+    /// e.g. the SVO Filter Profile Service.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, as are any
+    /// further columns.
+    pub fn from_text(text: &str) -> Result<Self, anyhow::Error> {
+        let (wavelength, throughput) = read_columns(text, 0, 1)?;
+        Self::new(
+            &wavelength
+                .into_iter()
+                .map(Angstrom::new)
+                .collect::<Vec<_>>(),
+            &throughput,
+        )
+    }
+
+    /// Pivot wavelength, $\sqrt{\int R \lambda d\lambda / \int R / \lambda d\lambda}$,
+    /// which converts between $f_\nu$ and $f_\lambda$ averaged over the band.
+    pub fn pivot_wavelength(&self) -> Angstrom {
+        let numerator = self.integrate(|wavelength| wavelength, []);
+        let denominator = self.integrate(|wavelength| 1. / wavelength, []);
+        Angstrom::new((numerator / denominator).sqrt())
+    }
+
+    /// $\int f(\lambda) R(\lambda) d\lambda$ by the trapezoidal rule.
+    ///
+    /// The integral is evaluated on the filter wavelengths together with
+    /// any `nodes` within the filter, e.g. those of a tabulated spectrum.
+    pub(crate) fn integrate<F, I>(&self, f: F, nodes: I) -> f64
+    where
+        F: Fn(f64) -> f64,
+        I: IntoIterator<Item = f64>,
+    {
+        let (min, max) = self.wavelength_range();
+        let mut grid = self.throughput.x().to_vec();
+        grid.extend(nodes.into_iter().filter(|x| *x > min.0 && *x < max.0));
+        grid.sort_by(f64::total_cmp);
+        grid.dedup();
+
+        let integrand = |x: f64| f(x) * self.transmission(Angstrom::new(x));
+        grid.windows(2)
+            .map(|w| 0.5 * (w[1] - w[0]) * (integrand(w[0]) + integrand(w[1])))
+            .sum()
+    }
+}
+
+/// A rest-frame spectral energy distribution $L_\lambda$.
+///
+/// The spectrum is interpolated linearly and is only defined within the
+/// tabulated wavelengths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sed {
+    /// A descriptive name.
+    pub name: Option<String>,
+
+    /// Attenuation by the intergalactic medium applied when the spectrum
+    /// is redshifted, if any.
+    pub igm: Option<IgmModel>,
+
+    /// Spectral luminosity density (erg/s/Angstrom) as a function of
+    /// wavelength in Angstroms.
+    luminosity: LinearInterpolation,
+}
+
+impl Sed {
+    /// Instantiate a spectrum from its `luminosity` at increasing rest-frame `wavelength`s.
+    pub fn new(
+        wavelength: &[Angstrom],
+        luminosity: &[ErgPerSecPerAngstrom],
+    ) -> Result<Self, anyhow::Error> {
+        if wavelength.first().is_some_and(|w| w.0 <= 0.) {
+            return Err(anyhow!("SED wavelengths must be positive"));
+        }
+        if luminosity.iter().any(|l| l.0 < 0.) {
+            return Err(anyhow!("SED luminosities must be non-negative"));
+        }
+        let luminosity = LinearInterpolation::new(
+            wavelength.iter().map(|w| w.0).collect(),
+            luminosity.iter().map(|l| l.0).collect(),
+        )?;
+        Ok(Self {
+            name: None,
+            igm: None,
+            luminosity,
+        })
+    }
+
+    /// Set a descriptive name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Attenuate the spectrum by the intergalactic medium in front of the
+    /// source when it is redshifted, e.g. for magnitudes at $z > 2$.
+    pub fn with_igm(mut self, igm: IgmModel) -> Self {
+        self.igm = Some(igm);
+        self
+    }
+
+    /// Shortest and longest tabulated rest-frame wavelengths.
+    pub fn wavelength_range(&self) -> (Angstrom, Angstrom) {
+        let wavelength = self.luminosity.x();
+        (
+            Angstrom::new(wavelength[0]),
+            Angstrom::new(wavelength[wavelength.len() - 1]),
+        )
+    }
+
+    /// Spectral luminosity density at rest-frame `wavelength`, or `None`
+    /// outside the tabulated wavelengths.
+    pub fn luminosity(&self, wavelength: Angstrom) -> Option<ErgPerSecPerAngstrom> {
+        self.luminosity
+            .eval(wavelength.0)
+            .map(ErgPerSecPerAngstrom::new)
+    }
+
+    /// Read a whitespace separated table with rest-frame wavelength in
+    /// Angstroms in the first column and $L_\lambda$ in erg/s/Angstrom in the second.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, as are any
+    /// further columns.
+    pub fn from_text(text: &str) -> Result<Self, anyhow::Error> {
+        let (wavelength, luminosity) = read_columns(text, 0, 1)?;
+        Self::new(
+            &wavelength
+                .into_iter()
+                .map(Angstrom::new)
+                .collect::<Vec<_>>(),
+            &luminosity
+                .into_iter()
+                .map(ErgPerSecPerAngstrom::new)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Photon-weighted integral $\int \lambda L_\lambda(\lambda / s) R(\lambda) d\lambda$
+    /// of the spectrum stretched by `stretch` through `filter`.
+    ///
+    /// With `stretch` $= 1+z$ this is the observed-frame band luminosity of
+    /// the redshifted spectrum, up to the $(1+z)$ and distance dimming factors.
+    pub(crate) fn band_integral(
+        &self,
+        filter: &Filter,
+        stretch: f64,
+    ) -> Result<f64, anyhow::Error> {
+        let igm = self.igm.map(|igm| (igm, Redshift::new(stretch - 1.)));
+        photon_integral(&self.luminosity, filter, stretch, igm, "SED")
+    }
+}
+
+/// An observed spectrum $f_\lambda$, such as that of a photometric standard.
+///
+/// The spectrum is interpolated linearly and is only defined within the
+/// tabulated wavelengths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObservedSpectrum {
+    /// A descriptive name.
+    pub name: Option<String>,
+
+    /// Spectral flux density (erg/s/cm^2/Angstrom) as a function of
+    /// wavelength in Angstroms.
+    flux: LinearInterpolation,
+}
+
+impl ObservedSpectrum {
+    /// Instantiate a spectrum from its `flux` at increasing `wavelength`s.
+    pub fn new(
+        wavelength: &[Angstrom],
+        flux: &[ErgPerSecPerCm2PerAngstrom],
+    ) -> Result<Self, anyhow::Error> {
+        if wavelength.first().is_some_and(|w| w.0 <= 0.) {
+            return Err(anyhow!("spectrum wavelengths must be positive"));
+        }
+        if flux.iter().any(|f| f.0 < 0.) {
+            return Err(anyhow!("spectrum fluxes must be non-negative"));
+        }
+        let flux = LinearInterpolation::new(
+            wavelength.iter().map(|w| w.0).collect(),
+            flux.iter().map(|f| f.0).collect(),
+        )?;
+        Ok(Self { name: None, flux })
+    }
+
+    /// Read a whitespace separated table with wavelength in Angstroms in the
+    /// first column and $f_\lambda$ in erg/s/cm^2/Angstrom in the second,
+    /// e.g. the CALSPEC spectrum of Vega.
+    ///
+    /// Blank lines and lines starting with `#` are skipped, as are any
+    /// further columns.
+    pub fn from_text(text: &str) -> Result<Self, anyhow::Error> {
+        let (wavelength, flux) = read_columns(text, 0, 1)?;
+        Self::new(
+            &wavelength
+                .into_iter()
+                .map(Angstrom::new)
+                .collect::<Vec<_>>(),
+            &flux
+                .into_iter()
+                .map(ErgPerSecPerCm2PerAngstrom::new)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// Set a descriptive name.
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Band-averaged spectral flux density $\langle f_\nu \rangle$ in `filter`.
+    pub fn band_flux_density(&self, filter: &Filter) -> Result<Jansky, anyhow::Error> {
+        let integral = photon_integral(&self.flux, filter, 1., None, "spectrum")?;
+        Ok(mean_flux_density(integral, filter).into())
+    }
+}
+
+/// Band-averaged spectral flux density $\langle f_\nu \rangle$ in `filter`
+/// of the rest-frame spectrum `sed` of a source at redshift `z`.
+///
+/// The observed spectrum is $f_\lambda(\lambda) = L_\lambda(\lambda / (1+z)) / (4 \pi d_L^2 (1+z))$.
+pub fn band_flux_density<C: Distances>(
+    cosmology: &C,
+    sed: &Sed,
+    filter: &Filter,
+    z: Redshift,
+) -> Result<Jansky, anyhow::Error> {
+    if z.0.is_nan() || z.0 <= 0. {
+        return Err(anyhow!(
+            "redshift must be positive to compute observed fluxes"
+        ));
+    }
+    // 1 m^2 = 1e4 cm^2
+    let area = luminosity_area(cosmology, z) * 1e4;
+    let integral = sed.band_integral(filter, 1. + z.0)? / ((1. + z.0) * area);
+    Ok(mean_flux_density(integral, filter).into())
+}
+
+/// AB magnitude in `filter` of the rest-frame spectrum `sed` of a source at redshift `z`.
+pub fn ab_magnitude<C: Distances>(
+    cosmology: &C,
+    sed: &Sed,
+    filter: &Filter,
+    z: Redshift,
+) -> Result<ABMagnitude, anyhow::Error> {
+    Ok(band_flux_density(cosmology, sed, filter, z)?.into())
+}
+
+/// Vega magnitude in `filter` of the rest-frame spectrum `sed` of a source
+/// at redshift `z`, given the observed spectrum of Vega.
+pub fn vega_magnitude<C: Distances>(
+    cosmology: &C,
+    sed: &Sed,
+    filter: &Filter,
+    z: Redshift,
+    vega: &ObservedSpectrum,
+) -> Result<VegaMagnitude, anyhow::Error> {
+    let flux = band_flux_density(cosmology, sed, filter, z)?;
+    let vega_flux = vega.band_flux_density(filter)?;
+    Ok(VegaMagnitude::new(-2.5 * (flux.0 / vega_flux.0).log10()))
+}
+
+/// AB color $m_\mathrm{blue} - m_\mathrm{red}$ of the rest-frame spectrum
+/// `sed` redshifted to `z`.
+///
+/// Colors do not depend on the distance, so no cosmology is needed.
+pub fn color(
+    sed: &Sed,
+    blue: &Filter,
+    red: &Filter,
+    z: Redshift,
+) -> Result<DimensionlessFloat, anyhow::Error> {
+    let blue_flux = mean_flux_density(sed.band_integral(blue, 1. + z.0)?, blue);
+    let red_flux = mean_flux_density(sed.band_integral(red, 1. + z.0)?, red);
+    Ok(DimensionlessFloat::new(
+        -2.5 * (blue_flux.0 / red_flux.0).log10(),
+    ))
+}
+
+/// AB colors $m_\mathrm{blue} - m_\mathrm{red}$ of the rest-frame spectrum
+/// `sed` at each of the redshifts `z`.
+pub fn color_track(
+    sed: &Sed,
+    blue: &Filter,
+    red: &Filter,
+    z: &[Redshift],
+) -> Result<Vec<DimensionlessFloat>, anyhow::Error> {
+    z.iter().map(|z| color(sed, blue, red, *z)).collect()
+}
+
+/// $\int \lambda f(\lambda / s) R(\lambda) d\lambda$ for a tabulated
+/// spectrum `f` stretched by `stretch`, which must cover `filter`, and
+/// optionally attenuated by the IGM in front of a source at the given redshift.
+fn photon_integral(
+    spectrum: &LinearInterpolation,
+    filter: &Filter,
+    stretch: f64,
+    igm: Option<(IgmModel, Redshift)>,
+    description: &str,
+) -> Result<f64, anyhow::Error> {
+    let (min, max) = filter.wavelength_range();
+    let wavelength = spectrum.x();
+    let (spectrum_min, spectrum_max) = (wavelength[0], wavelength[wavelength.len() - 1]);
+    if min.0 / stretch < spectrum_min || max.0 / stretch > spectrum_max {
+        return Err(anyhow!(
+            "{} covers {}-{} A but the filter needs {:.1}-{:.1} A",
+            description,
+            spectrum_min,
+            spectrum_max,
+            min.0 / stretch,
+            max.0 / stretch
+        ));
+    }
+
+    // Resolve the absorption edges, on either side of which the
+    // transmission is discontinuous
+    let edges = igm.map_or(vec![], |(igm, z)| igm.edges(z));
+    let nodes = wavelength
+        .iter()
+        .map(|x| x * stretch)
+        .chain(edges.iter().flat_map(|edge| [edge * (1. - 1e-9), *edge]));
+    let transmission = |wavelength: f64| {
+        igm.map_or(1., |(igm, z)| {
+            igm.transmission(Angstrom::new(wavelength), z)
+        })
+    };
+    Ok(filter.integrate(
+        |wavelength| {
+            wavelength
+                * spectrum.eval(wavelength / stretch).unwrap_or(0.)
+                * transmission(wavelength)
+        },
+        nodes,
+    ))
+}
+
+/// $\langle f_\nu \rangle = \int \lambda f_\lambda R d\lambda / (c \int R / \lambda d\lambda)$
+/// from the photon-weighted integral of $f_\lambda$ in erg/s/cm^2/Angstrom.
+fn mean_flux_density(photon_integral: f64, filter: &Filter) -> ErgPerSecPerCm2PerHz {
+    let c_angstrom_per_second = C_M_PER_S / ANGSTROM_TO_METER;
+    let normalization = c_angstrom_per_second * filter.integrate(|wavelength| 1. / wavelength, []);
+    ErgPerSecPerCm2PerHz::new(photon_integral / normalization)
+}
+
+/// Filters and spectra shared by the tests of this module and of [`crate::kcorrection`].
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use super::*;
+
+    /// A top hat filter sampled every Angstrom, with one Angstrom wings.
+    pub(crate) fn top_hat(min: f64, max: f64) -> Filter {
+        let wavelength: Vec<Angstrom> = (min as i64 - 1..=max as i64 + 1)
+            .map(|w| Angstrom::new(w as f64))
+            .collect();
+        let throughput: Vec<f64> = wavelength
+            .iter()
+            .map(|w| if w.0 < min || w.0 > max { 0. } else { 1. })
+            .collect();
+        Filter::new(&wavelength, &throughput).unwrap()
+    }
+
+    /// $f_\nu \propto \nu^\alpha$ normalized to `l_nu` erg/s/Hz at 5000 A,
+    /// tabulated from 500 A to 27000 A.
+    pub(crate) fn power_law_sed(alpha: f64, l_nu: f64) -> Sed {
+        let c_angstrom_per_second = C_M_PER_S / ANGSTROM_TO_METER;
+        let wavelength: Vec<Angstrom> = (0..=4000)
+            .map(|i| Angstrom::new(500. * 1.001_f64.powi(i)))
+            .collect();
+        let luminosity: Vec<ErgPerSecPerAngstrom> = wavelength
+            .iter()
+            .map(|w| {
+                let l_nu = l_nu * (w.0 / 5000.).powf(-alpha);
+                ErgPerSecPerAngstrom::new(l_nu * c_angstrom_per_second / w.0.powi(2))
+            })
+            .collect();
+        Sed::new(&wavelength, &luminosity).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        kcorrection,
+        photometry::test_helpers::{power_law_sed, top_hat},
+        FLRWCosmology, WattPerHz,
+    };
+
+    #[test]
+    fn filter_curves() {
+        let filter = top_hat(4000., 5000.);
+        assert_eq!(filter.transmission(Angstrom::new(4500.)), 1.);
+        assert_eq!(filter.transmission(Angstrom::new(3999.5)), 0.5);
+        assert_eq!(filter.transmission(Angstrom::new(6000.)), 0.);
+        // The pivot wavelength of a top hat from a to b is
+        // sqrt((b^2 - a^2) / (2 ln(b / a)))
+        let pivot = filter.pivot_wavelength().0;
+        let expected = (4.5e6 / 1.25_f64.ln()).sqrt();
+        assert!((pivot - expected).abs() < 1.);
+
+        assert!(Filter::new(&[Angstrom::new(1.), Angstrom::new(2.)], &[0., 0.]).is_err());
+        assert!(Filter::new(&[Angstrom::new(2.), Angstrom::new(1.)], &[1., 1.]).is_err());
+        assert!(Filter::new(&[Angstrom::new(-1.), Angstrom::new(1.)], &[1., 1.]).is_err());
+    }
+
+    #[test]
+    fn band_integrals_of_redshifted_spectra() {
+        let wavelength = [1000., 10000.].map(Angstrom::new);
+        let sed = Sed::new(&wavelength, &[ErgPerSecPerAngstrom::new(2.); 2]).unwrap();
+        assert_eq!(sed.luminosity(Angstrom::new(5000.)).unwrap().0, 2.);
+        assert_eq!(sed.luminosity(Angstrom::new(500.)), None);
+
+        // A flat spectrum gives 2 * (5000^2 - 4000^2) / 2 plus the filter wings
+        let filter = top_hat(4000., 5000.);
+        let integral = sed.band_integral(&filter, 1.).unwrap();
+        assert!((integral / 9e6 - 1.).abs() < 2e-3);
+        assert_eq!(sed.band_integral(&filter, 2.).unwrap(), integral);
+        assert!(sed.band_integral(&filter, 5.).is_err());
+    }
+
+    #[test]
+    fn read_filters_and_spectra() {
+        let filter = Filter::from_text(
+            "
+            # wavelength [A]  throughput
+            4000  0.0
+            4500  0.8  extra
+            5000  0.0
+            ",
+        )
+        .unwrap()
+        .with_name("triangle");
+        assert_eq!(filter.transmission(Angstrom::new(4250.)), 0.4);
+        assert_eq!(filter.name.as_deref(), Some("triangle"));
+        assert!(Filter::from_text("4000 0.0\n3000 1.0").is_err());
+
+        let sed = Sed::from_text("1000 1e40\n2000 2e40").unwrap();
+        assert_eq!(sed.luminosity(Angstrom::new(1500.)).unwrap().0, 1.5e40);
+        let spectrum = ObservedSpectrum::from_text("1000 1e-9\n2000 2e-9").unwrap();
+        assert!(spectrum.band_flux_density(&filter).is_err());
+    }
+
+    #[test]
+    fn magnitudes_of_redshifted_spectra() {
+        let cosmology = FLRWCosmology::two_component(0.3, 0.7, 70.);
+        let filter = top_hat(6000., 7000.);
+        let z = Redshift::new(1.);
+
+        // A flat f_nu spectrum is observed with f_nu = (1+z) L_nu / (4 pi d_L^2)
+        let l_nu = 1e29;
+        let sed = power_law_sed(0., l_nu);
+        let expected: Jansky = cosmology
+            .spectral_flux_density(WattPerHz::new(l_nu * 1e-7), z)
+            .into();
+        let flux = band_flux_density(&cosmology, &sed, &filter, z).unwrap();
+        assert!((flux.0 / expected.0 - 1.).abs() < 1e-6);
+        let m = ab_magnitude(&cosmology, &sed, &filter, z).unwrap();
+        assert!((m.0 - ABMagnitude::from(expected).0).abs() < 1e-6);
+        assert!(ab_magnitude(&cosmology, &sed, &filter, Redshift::zero()).is_err());
+
+        // Against a standard ten times brighter
+        let vega = ObservedSpectrum::new(
+            &[5000., 8000.].map(Angstrom::new),
+            &[ErgPerSecPerCm2PerAngstrom::new(1.); 2],
+        )
+        .unwrap();
+        let vega_flux = vega.band_flux_density(&filter).unwrap();
+        let scale = 10. * flux.0 / vega_flux.0;
+        let vega = ObservedSpectrum::new(
+            &[5000., 8000.].map(Angstrom::new),
+            &[ErgPerSecPerCm2PerAngstrom::new(scale); 2],
+        )
+        .unwrap();
+        let m = vega_magnitude(&cosmology, &sed, &filter, z, &vega).unwrap();
+        assert!((m.0 - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn lyman_break_dropouts() {
+        let u = top_hat(3200., 3900.);
+        let g = top_hat(4100., 5400.);
+        let sed = power_law_sed(0., 1.);
+        let attenuated = sed.clone().with_igm(IgmModel::Inoue2014);
+
+        // From z~3 the Lyman limit enters the u band and the break deepens
+        let z = [0.5, 3., 3.5].map(Redshift::new);
+        let colors = color_track(&attenuated, &u, &g, &z).unwrap();
+        assert!(colors[0].0.abs() < 1e-6);
+        assert!(colors[1].0 > 0.5);
+        assert!(colors[2].0 > colors[1].0 + 1.);
+        assert!(color(&sed, &u, &g, z[1]).unwrap().0.abs() < 1e-6);
+
+        // The IGM only acts on the redshifted spectrum
+        let k = kcorrection::from_sed(&attenuated, &g, z[1]).unwrap();
+        assert!(k.0 > kcorrection::from_sed(&sed, &g, z[1]).unwrap().0);
+    }
+
+    #[test]
+    fn color_tracks() {
+        let blue = top_hat(4000., 5000.);
+        let red = top_hat(6000., 7000.);
+        let z: Vec<Redshift> = [0., 0.5, 1., 2.].map(Redshift::new).to_vec();
+
+        // Power laws have the same color at every redshift
+        let flat = color_track(&power_law_sed(0., 1.), &blue, &red, &z).unwrap();
+        assert!(flat.iter().all(|color| color.0.abs() < 1e-6));
+        let red_colors = color_track(&power_law_sed(-1., 1.), &blue, &red, &z).unwrap();
+        assert!(red_colors[0].0 > 0.3);
+        assert!(red_colors
+            .iter()
+            .all(|color| (color.0 - red_colors[0].0).abs() < 1e-4));
+    }
+}
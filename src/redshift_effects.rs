@@ -0,0 +1,119 @@
+//! Effects of the redshift on observed light, independent of the cosmology.
+//!
+//! Wavelengths and durations are stretched by $1+z$, frequencies and photon
+//! energies are reduced by $1+z$, and the bolometric surface brightness of a
+//! source is dimmed by $(1+z)^4$ ([Tolman 1930]).
+//!
+//! [Tolman 1930]: <https://doi.org/10.1073/pnas.16.7.511>
+use crate::{
+    eV,
+    units::{length::Wavelength, PositiveFloat},
+    DimensionlessFloat, DimensionlessPositiveFloat, FloatingPointUnit, Hertz, Redshift, Seconds,
+};
+
+/// Factor $1+z$ by which durations and wavelengths are stretched.
+pub fn time_dilation_factor(z: Redshift) -> DimensionlessPositiveFloat {
+    PositiveFloat(1. + z.0)
+}
+
+/// Duration in the observer frame of an interval `rest_frame` at the source,
+/// e.g. the rise time of a supernova light curve.
+pub fn observed_duration(rest_frame: Seconds, z: Redshift) -> Seconds {
+    Seconds::new(rest_frame.0 * (1. + z.0))
+}
+
+/// Duration at the source of an interval `observed` in the observer frame.
+pub fn rest_frame_duration(observed: Seconds, z: Redshift) -> Seconds {
+    Seconds::new(observed.0 / (1. + z.0))
+}
+
+/// Observed wavelength of light emitted at the wavelength `rest_frame`.
+pub fn observed_wavelength<L: Wavelength>(rest_frame: L, z: Redshift) -> L {
+    L::new(rest_frame.inner() * (1. + z.0))
+}
+
+/// Rest-frame wavelength of light observed at the wavelength `observed`.
+pub fn rest_frame_wavelength<L: Wavelength>(observed: L, z: Redshift) -> L {
+    L::new(observed.inner() / (1. + z.0))
+}
+
+/// Observed frequency of light emitted at the frequency `rest_frame`.
+pub fn observed_frequency(rest_frame: Hertz, z: Redshift) -> Hertz {
+    Hertz::new(rest_frame.0 / (1. + z.0))
+}
+
+/// Rest-frame frequency of light observed at the frequency `observed`.
+pub fn rest_frame_frequency(observed: Hertz, z: Redshift) -> Hertz {
+    Hertz::new(observed.0 * (1. + z.0))
+}
+
+/// Observed energy of a photon emitted with the energy `rest_frame`.
+pub fn observed_energy(rest_frame: eV, z: Redshift) -> eV {
+    eV::new(rest_frame.0 / (1. + z.0))
+}
+
+/// Rest-frame energy of a photon observed with the energy `observed`.
+pub fn rest_frame_energy(observed: eV, z: Redshift) -> eV {
+    eV::new(observed.0 * (1. + z.0))
+}
+
+/// Factor $(1+z)^{-4}$ by which the bolometric surface brightness is dimmed.
+pub fn tolman_dimming_factor(z: Redshift) -> DimensionlessPositiveFloat {
+    PositiveFloat((1. + z.0).powi(-4))
+}
+
+/// Bolometric surface brightness dimming $10 \log_{10}(1+z)$ in mag/arcsec².
+///
+/// This is added to the rest-frame surface brightness. For a surface
+/// brightness in a band the dimming depends on the spectrum and the
+/// K-correction (see [`crate::kcorrection`]) must be added as well.
+pub fn surface_brightness_dimming(z: Redshift) -> DimensionlessFloat {
+    DimensionlessFloat::new(10. * (1. + z.0).log10())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Angstrom, Micrometer, Nanometer};
+
+    #[test]
+    fn stretching_and_redshifting() {
+        let z = Redshift::new(1.5);
+
+        let rest = Seconds::new(20. * 86400.);
+        let observed = observed_duration(rest, z);
+        assert_eq!(observed, Seconds::new(50. * 86400.));
+        assert_eq!(rest_frame_duration(observed, z), rest);
+        assert_eq!(time_dilation_factor(z).0, 2.5);
+
+        // Lyman alpha at z = 1.5 lands at 304 nm
+        let lyman_alpha = Angstrom::new(1215.67);
+        let observed = observed_wavelength(lyman_alpha, z);
+        let observed_nm: Nanometer = observed.into();
+        assert!((observed_nm.0 - 303.9175).abs() < 1e-9);
+        assert!((rest_frame_wavelength(observed, z).0 - lyman_alpha.0).abs() < 1e-9);
+        let h_alpha: Micrometer = Angstrom::new(6564.6).into();
+        assert!((observed_wavelength(h_alpha, z).0 - 1.64115).abs() < 1e-12);
+
+        // The 21 cm line
+        let rest = Hertz::new(1.420405751e9);
+        let observed = observed_frequency(rest, z);
+        assert!((observed.0 - 5.681623004e8).abs() < 1.);
+        assert!((rest_frame_frequency(observed, z).0 - rest.0).abs() < 1e-6);
+
+        let rest = eV::new(6400.);
+        assert_eq!(observed_energy(rest, z), eV::new(2560.));
+        assert_eq!(rest_frame_energy(eV::new(2560.), z), rest);
+    }
+
+    #[test]
+    fn tolman_dimming() {
+        let z = Redshift::new(1.);
+        assert_eq!(tolman_dimming_factor(z).0, 1. / 16.);
+        // The dimming in magnitudes matches the bolometric factor
+        let from_factor = -2.5 * tolman_dimming_factor(z).0.log10();
+        assert!((surface_brightness_dimming(z).0 - from_factor).abs() < 1e-12);
+        assert!((surface_brightness_dimming(z).0 - 3.0103).abs() < 1e-4);
+        assert_eq!(surface_brightness_dimming(Redshift::zero()).0, 0.);
+    }
+}
@@ -1,26 +1,37 @@
 use anyhow::anyhow;
 
+pub mod angle;
+pub mod density;
 pub mod dimensionless;
 pub mod energy;
+pub mod flux;
+pub mod frequency;
+pub mod hubble;
 pub mod length;
+pub mod little_h;
+pub mod luminosity;
 pub(crate) mod macros;
+pub mod magnitude;
 pub mod mass;
+pub(crate) mod symbols;
 pub mod temperature;
 pub mod time;
 pub mod traits;
 
 pub use dimensionless::DimensionlessFloat;
+pub use hubble::KmPerSecPerMpc;
+pub use little_h::{H2MsunPerMpc3, HInvMpc, HInvMpc3, HInvSolarMass, LittleHScaled};
 pub use traits::FloatingPointUnit;
 
 // Continuous positive quantities that are dimensionless (e.g. ratios like the omegas)
 pub type DimensionlessPositiveFloat = PositiveFloat;
 
 // Hubble parameter units
-pub type KmPerSecPerMpc = f64;
 pub type HInvKmPerSecPerMpc = f64;
 
 // Densities
-pub type KilogramsPerMeter3 = PositiveFloat;
+#[deprecated(note = "use `KilogramPerMeter3` instead")]
+pub type KilogramsPerMeter3 = density::KilogramPerMeter3;
 
 // Constant units
 // TODO: Work out a better way to handle types for composite unit information
@@ -31,13 +42,30 @@ pub type JouleSeconds = f64;
 pub type JoulePerMeter3Kelvin4 = f64;
 pub type WattsPerMeters2Kelvin4 = f64;
 pub type JoulePerKelvin = f64;
-pub type Mpc3 = f64;
-pub type HInvMpc = f64;
 
 /// Represents continuous physical quantities that _cannot_ be negative.
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "f64", into = "f64")
+)]
 pub struct PositiveFloat(pub f64);
 
+impl TryFrom<f64> for PositiveFloat {
+    type Error = anyhow::Error;
+
+    fn try_from(x: f64) -> Result<Self, Self::Error> {
+        Self::new(x)
+    }
+}
+
+impl From<PositiveFloat> for f64 {
+    fn from(x: PositiveFloat) -> Self {
+        x.0
+    }
+}
+
 impl PositiveFloat {
     pub fn new(x: f64) -> Result<Self, anyhow::Error> {
         if x < 0. {
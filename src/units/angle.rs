@@ -0,0 +1,98 @@
+use std::ops::{Add, Sub};
+
+use crate::{
+    constants::PI,
+    units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit},
+};
+
+floating_point_unit_impl! { Radian, "rad", symbols::ANGLE }
+floating_point_unit_impl! { Degree, "deg", symbols::ANGLE }
+floating_point_unit_impl! { Arcminute, "arcmin", symbols::ANGLE }
+floating_point_unit_impl! { Arcsecond, "arcsec", symbols::ANGLE }
+floating_point_unit_impl! { Steradian, "sr", symbols::SOLID_ANGLE }
+floating_point_unit_impl! { SquareDegree, "deg^2", symbols::SOLID_ANGLE }
+
+// Conversions
+pub const DEGREES_PER_RADIAN: f64 = 180. / PI;
+pub const ARCMINUTES_PER_DEGREE: f64 = 60.;
+pub const ARCSECONDS_PER_DEGREE: f64 = 3600.;
+pub const SQUARE_DEGREES_PER_STERADIAN: f64 = DEGREES_PER_RADIAN * DEGREES_PER_RADIAN;
+
+/// Solid angle of the full sky.
+pub const FULL_SKY: Steradian = Steradian(4. * PI);
+
+impl From<Degree> for Radian {
+    fn from(deg: Degree) -> Self {
+        Radian(deg.0 / DEGREES_PER_RADIAN)
+    }
+}
+
+impl From<Arcminute> for Radian {
+    fn from(arcmin: Arcminute) -> Self {
+        Radian(arcmin.0 / (ARCMINUTES_PER_DEGREE * DEGREES_PER_RADIAN))
+    }
+}
+
+impl From<Arcsecond> for Radian {
+    fn from(arcsec: Arcsecond) -> Self {
+        Radian(arcsec.0 / (ARCSECONDS_PER_DEGREE * DEGREES_PER_RADIAN))
+    }
+}
+
+impl From<Radian> for Degree {
+    fn from(rad: Radian) -> Self {
+        Degree(rad.0 * DEGREES_PER_RADIAN)
+    }
+}
+
+impl From<Radian> for Arcminute {
+    fn from(rad: Radian) -> Self {
+        Arcminute(rad.0 * DEGREES_PER_RADIAN * ARCMINUTES_PER_DEGREE)
+    }
+}
+
+impl From<Radian> for Arcsecond {
+    fn from(rad: Radian) -> Self {
+        Arcsecond(rad.0 * DEGREES_PER_RADIAN * ARCSECONDS_PER_DEGREE)
+    }
+}
+
+impl From<SquareDegree> for Steradian {
+    fn from(deg2: SquareDegree) -> Self {
+        Steradian(deg2.0 / SQUARE_DEGREES_PER_STERADIAN)
+    }
+}
+
+impl From<Steradian> for SquareDegree {
+    fn from(sr: Steradian) -> Self {
+        SquareDegree(sr.0 * SQUARE_DEGREES_PER_STERADIAN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angle_conversions() {
+        let right_angle: Radian = Degree::new(90.).into();
+        assert!((right_angle.0 - PI / 2.).abs() < 1e-12);
+
+        let arcsec: Arcsecond = Radian::from(Degree::new(1.)).into();
+        assert!((arcsec.0 - 3600.).abs() < 1e-9);
+
+        let arcmin: Arcminute = Radian::from(Arcsecond::new(120.)).into();
+        assert!((arcmin.0 - 2.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn solid_angle_conversions() {
+        // The full sky is ~41253 square degrees
+        let full_sky: SquareDegree = FULL_SKY.into();
+        assert!(full_sky > SquareDegree::new(41252.9));
+        assert!(full_sky < SquareDegree::new(41253.0));
+
+        let sr: Steradian = full_sky.into();
+        assert!((sr.0 - FULL_SKY.0).abs() < 1e-12);
+    }
+}
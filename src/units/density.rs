@@ -0,0 +1,56 @@
+use std::ops::{Add, Sub};
+
+use crate::units::{
+    length::MPC_TO_METERS, macros::floating_point_unit_impl, mass::SOLAR_MASS_TO_KILOGRAMS,
+    symbols, traits::FloatingPointUnit,
+};
+
+floating_point_unit_impl! { KilogramPerMeter3, "kg/m^3", symbols::DENSITY }
+floating_point_unit_impl! { GramPerCm3, "g/cm^3", symbols::DENSITY }
+floating_point_unit_impl! { MsunPerMpc3, "Msun/Mpc^3", symbols::DENSITY }
+
+// Conversions
+pub const GRAM_PER_CM3_TO_KILOGRAM_PER_METER3: f64 = 1000.;
+
+impl From<GramPerCm3> for KilogramPerMeter3 {
+    fn from(density: GramPerCm3) -> Self {
+        KilogramPerMeter3::new(density.0 * GRAM_PER_CM3_TO_KILOGRAM_PER_METER3)
+    }
+}
+
+impl From<KilogramPerMeter3> for GramPerCm3 {
+    fn from(density: KilogramPerMeter3) -> Self {
+        GramPerCm3::new(density.0 / GRAM_PER_CM3_TO_KILOGRAM_PER_METER3)
+    }
+}
+
+impl From<MsunPerMpc3> for KilogramPerMeter3 {
+    fn from(density: MsunPerMpc3) -> Self {
+        KilogramPerMeter3::new(density.0 * SOLAR_MASS_TO_KILOGRAMS / MPC_TO_METERS.powi(3))
+    }
+}
+
+impl From<KilogramPerMeter3> for MsunPerMpc3 {
+    fn from(density: KilogramPerMeter3) -> Self {
+        MsunPerMpc3::new(density.0 * MPC_TO_METERS.powi(3) / SOLAR_MASS_TO_KILOGRAMS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn density_conversions() {
+        let water: KilogramPerMeter3 = GramPerCm3::one().into();
+        assert_eq!(water, KilogramPerMeter3::new(1000.));
+
+        // 1 Msun / Mpc^3 is ~6.77e-41 g/cm^3
+        let density: GramPerCm3 = KilogramPerMeter3::from(MsunPerMpc3::one()).into();
+        assert!(density > GramPerCm3::new(6.7e-41));
+        assert!(density < GramPerCm3::new(6.8e-41));
+
+        let back: MsunPerMpc3 = KilogramPerMeter3::from(density).into();
+        assert!((back.0 - 1.).abs() < 1e-9);
+    }
+}
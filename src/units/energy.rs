@@ -1,6 +1,22 @@
 use std::ops::{Add, Sub};
 
-use crate::units::{macros::floating_point_unit_impl, traits::FloatingPointUnit};
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
 
-floating_point_unit_impl! { eV }
-floating_point_unit_impl! { Joule }
+floating_point_unit_impl! { eV, "eV", symbols::ENERGY }
+floating_point_unit_impl! { Joule, "J", symbols::ENERGY }
+
+// Conversions
+/// Exact value of the electronvolt following the 2019 SI redefinition.
+pub const ELECTRONVOLT_TO_JOULES: f64 = 1.602176634e-19;
+
+impl From<eV> for Joule {
+    fn from(energy: eV) -> Self {
+        Joule::new(energy.0 * ELECTRONVOLT_TO_JOULES)
+    }
+}
+
+impl From<Joule> for eV {
+    fn from(energy: Joule) -> Self {
+        eV::new(energy.0 / ELECTRONVOLT_TO_JOULES)
+    }
+}
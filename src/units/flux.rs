@@ -0,0 +1,123 @@
+use std::ops::{Add, Sub};
+
+use crate::{
+    constants::C_M_PER_S,
+    units::{
+        length::{Angstrom, ANGSTROM_TO_METER},
+        macros::floating_point_unit_impl,
+        symbols,
+        traits::FloatingPointUnit,
+    },
+};
+
+// Bolometric fluxes
+floating_point_unit_impl! { WattPerMeter2, "W/m^2", symbols::FLUX }
+floating_point_unit_impl! { ErgPerSecPerCm2, "erg/s/cm^2", symbols::FLUX }
+
+// Spectral flux densities
+floating_point_unit_impl! { WattPerMeter2PerHz, "W/m^2/Hz", symbols::SPECTRAL_FLUX_PER_HZ }
+floating_point_unit_impl! { ErgPerSecPerCm2PerHz, "erg/s/cm^2/Hz", symbols::SPECTRAL_FLUX_PER_HZ }
+floating_point_unit_impl! { Jansky, "Jy", symbols::SPECTRAL_FLUX_PER_HZ }
+floating_point_unit_impl! { ErgPerSecPerCm2PerAngstrom, "erg/s/cm^2/A", symbols::SPECTRAL_FLUX_PER_ANGSTROM }
+
+// Conversions
+pub const WATT_PER_METER2_TO_ERG_PER_SEC_PER_CM2: f64 = 1e3;
+pub const JANSKY_TO_WATT_PER_METER2_PER_HZ: f64 = 1e-26;
+pub const JANSKY_TO_ERG_PER_SEC_PER_CM2_PER_HZ: f64 = 1e-23;
+
+impl From<ErgPerSecPerCm2> for WattPerMeter2 {
+    fn from(flux: ErgPerSecPerCm2) -> Self {
+        WattPerMeter2::new(flux.0 / WATT_PER_METER2_TO_ERG_PER_SEC_PER_CM2)
+    }
+}
+
+impl From<WattPerMeter2> for ErgPerSecPerCm2 {
+    fn from(flux: WattPerMeter2) -> Self {
+        ErgPerSecPerCm2::new(flux.0 * WATT_PER_METER2_TO_ERG_PER_SEC_PER_CM2)
+    }
+}
+
+impl From<Jansky> for WattPerMeter2PerHz {
+    fn from(flux: Jansky) -> Self {
+        WattPerMeter2PerHz::new(flux.0 * JANSKY_TO_WATT_PER_METER2_PER_HZ)
+    }
+}
+
+impl From<WattPerMeter2PerHz> for Jansky {
+    fn from(flux: WattPerMeter2PerHz) -> Self {
+        Jansky::new(flux.0 / JANSKY_TO_WATT_PER_METER2_PER_HZ)
+    }
+}
+
+impl From<Jansky> for ErgPerSecPerCm2PerHz {
+    fn from(flux: Jansky) -> Self {
+        ErgPerSecPerCm2PerHz::new(flux.0 * JANSKY_TO_ERG_PER_SEC_PER_CM2_PER_HZ)
+    }
+}
+
+impl From<ErgPerSecPerCm2PerHz> for Jansky {
+    fn from(flux: ErgPerSecPerCm2PerHz) -> Self {
+        Jansky::new(flux.0 / JANSKY_TO_ERG_PER_SEC_PER_CM2_PER_HZ)
+    }
+}
+
+impl From<ErgPerSecPerCm2PerHz> for WattPerMeter2PerHz {
+    fn from(flux: ErgPerSecPerCm2PerHz) -> Self {
+        Jansky::from(flux).into()
+    }
+}
+
+impl From<WattPerMeter2PerHz> for ErgPerSecPerCm2PerHz {
+    fn from(flux: WattPerMeter2PerHz) -> Self {
+        Jansky::from(flux).into()
+    }
+}
+
+/// Speed of light in Angstrom per second.
+fn c_angstrom_per_s() -> f64 {
+    C_M_PER_S / ANGSTROM_TO_METER
+}
+
+impl ErgPerSecPerCm2PerHz {
+    /// Convert to a flux density per unit wavelength at `wavelength`
+    /// using $f_\lambda = f_\nu c / \lambda^2$.
+    pub fn to_per_angstrom(&self, wavelength: Angstrom) -> ErgPerSecPerCm2PerAngstrom {
+        ErgPerSecPerCm2PerAngstrom::new(self.0 * c_angstrom_per_s() / wavelength.powi(2))
+    }
+}
+
+impl ErgPerSecPerCm2PerAngstrom {
+    /// Convert to a flux density per unit frequency at `wavelength`
+    /// using $f_\nu = f_\lambda \lambda^2 / c$.
+    pub fn to_per_hz(&self, wavelength: Angstrom) -> ErgPerSecPerCm2PerHz {
+        ErgPerSecPerCm2PerHz::new(self.0 * wavelength.powi(2) / c_angstrom_per_s())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flux_conversions() {
+        let flux: ErgPerSecPerCm2 = WattPerMeter2::one().into();
+        assert_eq!(flux, ErgPerSecPerCm2::new(1000.));
+
+        let jy: WattPerMeter2PerHz = Jansky::one().into();
+        assert_eq!(jy, WattPerMeter2PerHz::new(1e-26));
+        let jy: ErgPerSecPerCm2PerHz = Jansky::one().into();
+        assert_eq!(jy, ErgPerSecPerCm2PerHz::new(1e-23));
+    }
+
+    #[test]
+    fn per_frequency_to_per_wavelength() {
+        // The AB zero point, 3631 Jy, is ~3.60e-9 erg/s/cm^2/A at 5500 A
+        let f_nu: ErgPerSecPerCm2PerHz = Jansky::new(3631.).into();
+        let f_lambda = f_nu.to_per_angstrom(Angstrom::new(5500.));
+        assert!(f_lambda > ErgPerSecPerCm2PerAngstrom::new(3.59e-9));
+        assert!(f_lambda < ErgPerSecPerCm2PerAngstrom::new(3.61e-9));
+
+        let back = f_lambda.to_per_hz(Angstrom::new(5500.));
+        assert!((back.0 / f_nu.0 - 1.).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,5 @@
+use std::ops::{Add, Sub};
+
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
+
+floating_point_unit_impl! { Hertz, "Hz", symbols::FREQUENCY }
@@ -1,15 +1,93 @@
 use std::ops::{Add, Sub};
 
-use crate::units::{macros::floating_point_unit_impl, traits::FloatingPointUnit};
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
 
-floating_point_unit_impl! { Meter }
-floating_point_unit_impl! { Kilometer }
-floating_point_unit_impl! { Mpc }
+floating_point_unit_impl! { Angstrom, "A", symbols::LENGTH }
+floating_point_unit_impl! { Nanometer, "nm", symbols::LENGTH }
+floating_point_unit_impl! { Micrometer, "um", symbols::LENGTH }
+floating_point_unit_impl! { Meter, "m", symbols::LENGTH }
+floating_point_unit_impl! { Kilometer, "km", symbols::LENGTH }
+floating_point_unit_impl! { Kpc, "kpc", symbols::LENGTH }
+floating_point_unit_impl! { Mpc, "Mpc", symbols::LENGTH }
+floating_point_unit_impl! { Mpc3, "Mpc^3", symbols::VOLUME }
+
+/// Lengths used for the wavelength of light, which are stretched by $1+z$.
+pub trait Wavelength: FloatingPointUnit {}
+
+impl Wavelength for Angstrom {}
+impl Wavelength for Nanometer {}
+impl Wavelength for Micrometer {}
+impl Wavelength for Meter {}
 
 // Conversions
+pub const ANGSTROM_TO_METER: f64 = 1e-10;
+pub const NANOMETER_TO_METER: f64 = 1e-9;
+pub const MICROMETER_TO_METER: f64 = 1e-6;
 pub const KILOMETER_TO_METER: f64 = 1000.;
-pub const MPC_TO_METERS: f64 = 3.086e+22;
-pub const MPC_TO_KILOMETERS: f64 = 3.086e+19;
+/// 1 Mpc = 10^6 * (648000 / pi) au, with the IAU 2012 exact au.
+pub const MPC_TO_METERS: f64 = 3.085677581491367e+22;
+pub const MPC_TO_KILOMETERS: f64 = 3.085677581491367e+19;
+pub const MPC_TO_KPC: f64 = 1e3;
+
+impl From<Angstrom> for Meter {
+    fn from(angstrom: Angstrom) -> Meter {
+        Meter(angstrom.0 * ANGSTROM_TO_METER)
+    }
+}
+
+impl From<Meter> for Angstrom {
+    fn from(meter: Meter) -> Angstrom {
+        Angstrom(meter.0 / ANGSTROM_TO_METER)
+    }
+}
+
+impl From<Nanometer> for Meter {
+    fn from(nm: Nanometer) -> Meter {
+        Meter(nm.0 * NANOMETER_TO_METER)
+    }
+}
+
+impl From<Meter> for Nanometer {
+    fn from(meter: Meter) -> Nanometer {
+        Nanometer(meter.0 / NANOMETER_TO_METER)
+    }
+}
+
+impl From<Micrometer> for Meter {
+    fn from(um: Micrometer) -> Meter {
+        Meter(um.0 * MICROMETER_TO_METER)
+    }
+}
+
+impl From<Meter> for Micrometer {
+    fn from(meter: Meter) -> Micrometer {
+        Micrometer(meter.0 / MICROMETER_TO_METER)
+    }
+}
+
+impl From<Angstrom> for Nanometer {
+    fn from(angstrom: Angstrom) -> Nanometer {
+        Nanometer(angstrom.0 * ANGSTROM_TO_METER / NANOMETER_TO_METER)
+    }
+}
+
+impl From<Nanometer> for Angstrom {
+    fn from(nm: Nanometer) -> Angstrom {
+        Angstrom(nm.0 * NANOMETER_TO_METER / ANGSTROM_TO_METER)
+    }
+}
+
+impl From<Angstrom> for Micrometer {
+    fn from(angstrom: Angstrom) -> Micrometer {
+        Micrometer(angstrom.0 * ANGSTROM_TO_METER / MICROMETER_TO_METER)
+    }
+}
+
+impl From<Micrometer> for Angstrom {
+    fn from(um: Micrometer) -> Angstrom {
+        Angstrom(um.0 * MICROMETER_TO_METER / ANGSTROM_TO_METER)
+    }
+}
 
 impl From<Kilometer> for Meter {
     fn from(km: Kilometer) -> Meter {
@@ -29,6 +107,18 @@ impl From<Mpc> for Kilometer {
     }
 }
 
+impl From<Mpc> for Kpc {
+    fn from(mpc: Mpc) -> Self {
+        Kpc(mpc.0 * MPC_TO_KPC)
+    }
+}
+
+impl From<Kpc> for Mpc {
+    fn from(kpc: Kpc) -> Self {
+        Mpc(kpc.0 / MPC_TO_KPC)
+    }
+}
+
 impl From<Meter> for Mpc {
     fn from(meter: Meter) -> Self {
         Mpc(meter.0 / MPC_TO_METERS)
@@ -0,0 +1,81 @@
+use std::ops::{Add, Sub};
+
+use crate::{
+    units::{
+        density::MsunPerMpc3,
+        length::{Mpc, Mpc3},
+        macros::floating_point_unit_impl,
+        mass::SolarMass,
+        symbols,
+        traits::FloatingPointUnit,
+    },
+    DimensionlessPositiveFloat,
+};
+
+/// Quantities expressed in units that carry a power of the dimensionless
+/// Hubble parameter $h$, e.g. $h^{-1}$ Mpc.
+///
+/// A value `x` of such a quantity corresponds to `x * h^H_POWER` in `Unit`,
+/// so converting in either direction requires a value for $h$.
+pub trait LittleHScaled: FloatingPointUnit {
+    /// The unit once the factor of $h$ has been removed.
+    type Unit: FloatingPointUnit;
+
+    /// The power of $h$ carried by this unit.
+    const H_POWER: i32;
+
+    /// Remove the factor of $h$.
+    fn to_unit(&self, h: DimensionlessPositiveFloat) -> Self::Unit
+    where
+        Self: std::marker::Sized,
+    {
+        Self::Unit::new(self.inner() * h.powf(Self::H_POWER as f64))
+    }
+
+    /// Express a quantity in units carrying a factor of $h$.
+    fn from_unit(value: Self::Unit, h: DimensionlessPositiveFloat) -> Self
+    where
+        Self: std::marker::Sized,
+    {
+        Self::new(value.inner() * h.powf(-Self::H_POWER as f64))
+    }
+}
+
+/// This macro implements [`LittleHScaled`] for a unit that is `$unit`
+/// scaled by $h^{power}$.
+macro_rules! little_h_unit_impl {
+    ($outer : ident, $unit : ident, $power : expr, $symbol : literal, $table : expr) => {
+        floating_point_unit_impl! { $outer, $symbol, $table }
+
+        impl LittleHScaled for $outer {
+            type Unit = $unit;
+            const H_POWER: i32 = $power;
+        }
+    };
+}
+
+little_h_unit_impl! { HInvMpc, Mpc, -1, "Mpc/h", symbols::H_INV_LENGTH }
+little_h_unit_impl! { HInvMpc3, Mpc3, -3, "(Mpc/h)^3", symbols::H_INV_VOLUME }
+little_h_unit_impl! { HInvSolarMass, SolarMass, -1, "Msun/h", symbols::H_INV_MASS }
+little_h_unit_impl! { H2MsunPerMpc3, MsunPerMpc3, 2, "h^2 Msun/Mpc^3", symbols::H2_DENSITY }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_through_little_h() {
+        let h = DimensionlessPositiveFloat::new(0.7).unwrap();
+
+        let d = HInvMpc::new(700.);
+        assert!((d.to_unit(h).0 - 1000.).abs() < 1e-9);
+        assert!((HInvMpc::from_unit(Mpc::new(1000.), h).0 - 700.).abs() < 1e-9);
+
+        let v = HInvMpc3::from_unit(Mpc3::new(1.), h);
+        assert!((v.0 - 0.343).abs() < 1e-12);
+        assert!((v.to_unit(h).0 - 1.).abs() < 1e-12);
+
+        let rho = H2MsunPerMpc3::new(1.);
+        assert!((rho.to_unit(h).0 - 0.49).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::{Add, Sub};
+
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
+
+// Bolometric luminosities
+floating_point_unit_impl! { Watt, "W", symbols::LUMINOSITY }
+floating_point_unit_impl! { ErgPerSecond, "erg/s", symbols::LUMINOSITY }
+floating_point_unit_impl! { SolarLuminosity, "Lsun", symbols::LUMINOSITY }
+
+// Spectral luminosity densities
+floating_point_unit_impl! { WattPerHz, "W/Hz", symbols::SPECTRAL_LUMINOSITY_PER_HZ }
+floating_point_unit_impl! { ErgPerSecPerHz, "erg/s/Hz", symbols::SPECTRAL_LUMINOSITY_PER_HZ }
+floating_point_unit_impl! { ErgPerSecPerAngstrom, "erg/s/A", symbols::SPECTRAL_LUMINOSITY_PER_ANGSTROM }
+
+// Conversions
+pub const WATT_TO_ERG_PER_SECOND: f64 = 1e7;
+/// Nominal solar luminosity from IAU 2015 Resolution B3.
+pub const SOLAR_LUMINOSITY_TO_WATTS: f64 = 3.828e26;
+
+impl From<ErgPerSecond> for Watt {
+    fn from(luminosity: ErgPerSecond) -> Self {
+        Watt::new(luminosity.0 / WATT_TO_ERG_PER_SECOND)
+    }
+}
+
+impl From<Watt> for ErgPerSecond {
+    fn from(luminosity: Watt) -> Self {
+        ErgPerSecond::new(luminosity.0 * WATT_TO_ERG_PER_SECOND)
+    }
+}
+
+impl From<SolarLuminosity> for Watt {
+    fn from(luminosity: SolarLuminosity) -> Self {
+        Watt::new(luminosity.0 * SOLAR_LUMINOSITY_TO_WATTS)
+    }
+}
+
+impl From<Watt> for SolarLuminosity {
+    fn from(luminosity: Watt) -> Self {
+        SolarLuminosity::new(luminosity.0 / SOLAR_LUMINOSITY_TO_WATTS)
+    }
+}
+
+impl From<ErgPerSecPerHz> for WattPerHz {
+    fn from(luminosity: ErgPerSecPerHz) -> Self {
+        WattPerHz::new(luminosity.0 / WATT_TO_ERG_PER_SECOND)
+    }
+}
+
+impl From<WattPerHz> for ErgPerSecPerHz {
+    fn from(luminosity: WattPerHz) -> Self {
+        ErgPerSecPerHz::new(luminosity.0 * WATT_TO_ERG_PER_SECOND)
+    }
+}
@@ -2,9 +2,67 @@
 /// exist on unit/dimension structs that behave like floating point units.
 ///
 /// You can add and subtract two values of the same unit.
+///
+/// Units are formatted with `Display` and parsed with `FromStr`. When a
+/// symbol and a table of symbols for the dimension (see
+/// [`crate::units::symbols`]) are given, the symbol is printed after the value
+/// and strings in any unit of the same dimension are converted on parse.
+/// The precision of the value is taken from the formatter, e.g. `{:.2}`.
+///
+/// With the `serde` feature, units are (de)serialized as bare numbers.
 macro_rules! floating_point_unit_impl {
     ($outer : ident) => {
+        floating_point_unit_impl! { @base $outer }
+
+        impl std::fmt::Display for $outer {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match f.precision() {
+                    Some(precision) => write!(f, "{:.*}", precision, self.0),
+                    None => write!(f, "{}", self.0),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $outer {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::units::symbols::parse_number(s).map($outer)
+            }
+        }
+    };
+    ($outer : ident, $symbol : literal, $table : expr) => {
+        floating_point_unit_impl! { @base $outer }
+
+        impl $outer {
+            /// Symbol used when formatting this unit.
+            pub const SYMBOL: &'static str = $symbol;
+        }
+
+        impl std::fmt::Display for $outer {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match f.precision() {
+                    Some(precision) => write!(f, "{:.*} {}", precision, self.0, $symbol),
+                    None => write!(f, "{} {}", self.0, $symbol),
+                }
+            }
+        }
+
+        impl std::str::FromStr for $outer {
+            type Err = anyhow::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $crate::units::symbols::parse_quantity(s, $symbol, $table).map($outer)
+            }
+        }
+    };
+    (@base $outer : ident) => {
         #[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         pub struct $outer(pub f64);
 
         impl FloatingPointUnit for $outer {
@@ -21,7 +79,7 @@ macro_rules! floating_point_unit_impl {
             type Output = $outer;
 
             fn add(self, b: $outer) -> $outer {
-                $outer((self.0.add(&b.0)))
+                $outer(self.0.add(&b.0))
             }
         }
 
@@ -29,7 +87,7 @@ macro_rules! floating_point_unit_impl {
             type Output = $outer;
 
             fn sub(self, b: $outer) -> $outer {
-                $outer((self.0.sub(&b.0)))
+                $outer(self.0.sub(&b.0))
             }
         }
     };
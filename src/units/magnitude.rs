@@ -0,0 +1,161 @@
+use std::{
+    marker::PhantomData,
+    ops::{Add, Sub},
+};
+
+use anyhow::anyhow;
+
+use crate::units::{
+    dimensionless::DimensionlessFloat, flux::Jansky, symbols, traits::FloatingPointUnit,
+};
+
+/// A photometric system, which fixes the zero point of its magnitudes.
+pub trait MagnitudeSystem {
+    /// Symbol used when formatting and parsing magnitudes in this system.
+    const SYMBOL: &'static str;
+}
+
+/// The AB system, in which a flat spectrum of 3631 Jy has magnitude zero in every band.
+#[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
+pub struct AB;
+
+/// The Vega system, in which Vega has magnitude zero in every band.
+#[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
+pub struct Vega;
+
+impl MagnitudeSystem for AB {
+    const SYMBOL: &'static str = "mag(AB)";
+}
+
+impl MagnitudeSystem for Vega {
+    const SYMBOL: &'static str = "mag(Vega)";
+}
+
+/// Spectral flux density of an AB magnitude of zero.
+pub const AB_ZERO_POINT_JANSKY: f64 = 3631.;
+
+/// An apparent or absolute magnitude in the photometric system `S`.
+///
+/// The system is a type parameter so that AB and Vega magnitudes cannot be
+/// mixed up: the offset between them depends on the band.
+#[derive(Clone, Copy, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent, bound = "")
+)]
+pub struct Magnitude<S: MagnitudeSystem>(
+    pub f64,
+    #[cfg_attr(feature = "serde", serde(skip))] PhantomData<S>,
+);
+
+pub type ABMagnitude = Magnitude<AB>;
+pub type VegaMagnitude = Magnitude<Vega>;
+
+impl<S: MagnitudeSystem> FloatingPointUnit for Magnitude<S> {
+    fn new(x: f64) -> Self {
+        Self(x, PhantomData)
+    }
+
+    fn inner(&self) -> f64 {
+        self.0
+    }
+}
+
+impl<S: MagnitudeSystem> Add<Magnitude<S>> for Magnitude<S> {
+    type Output = Magnitude<S>;
+
+    fn add(self, b: Magnitude<S>) -> Magnitude<S> {
+        Magnitude::new(self.0 + b.0)
+    }
+}
+
+impl<S: MagnitudeSystem> Sub<Magnitude<S>> for Magnitude<S> {
+    type Output = Magnitude<S>;
+
+    fn sub(self, b: Magnitude<S>) -> Magnitude<S> {
+        Magnitude::new(self.0 - b.0)
+    }
+}
+
+/// Offsets in magnitudes, such as the distance modulus or a K-correction.
+impl<S: MagnitudeSystem> Add<DimensionlessFloat> for Magnitude<S> {
+    type Output = Magnitude<S>;
+
+    fn add(self, offset: DimensionlessFloat) -> Magnitude<S> {
+        Magnitude::new(self.0 + offset.0)
+    }
+}
+
+impl<S: MagnitudeSystem> Sub<DimensionlessFloat> for Magnitude<S> {
+    type Output = Magnitude<S>;
+
+    fn sub(self, offset: DimensionlessFloat) -> Magnitude<S> {
+        Magnitude::new(self.0 - offset.0)
+    }
+}
+
+impl<S: MagnitudeSystem> std::fmt::Display for Magnitude<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {}", precision, self.0, S::SYMBOL),
+            None => write!(f, "{} {}", self.0, S::SYMBOL),
+        }
+    }
+}
+
+impl<S: MagnitudeSystem> std::str::FromStr for Magnitude<S> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, unit) = symbols::split_quantity(s);
+        let unit: String = unit.chars().filter(|c| !c.is_whitespace()).collect();
+        if !unit.is_empty() && unit != S::SYMBOL {
+            return Err(anyhow!("unknown unit '{}' for {}", unit, S::SYMBOL));
+        }
+        symbols::parse_number(number).map(Magnitude::new)
+    }
+}
+
+impl From<Jansky> for Magnitude<AB> {
+    fn from(flux: Jansky) -> Self {
+        Magnitude::new(-2.5 * (flux.0 / AB_ZERO_POINT_JANSKY).log10())
+    }
+}
+
+impl From<Magnitude<AB>> for Jansky {
+    fn from(magnitude: Magnitude<AB>) -> Self {
+        Jansky::new(AB_ZERO_POINT_JANSKY * 10_f64.powf(-0.4 * magnitude.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ab_magnitudes_and_flux_densities() {
+        let m: ABMagnitude = Jansky::new(AB_ZERO_POINT_JANSKY).into();
+        assert_eq!(m, ABMagnitude::zero());
+
+        // 23.9 AB is 1 microjansky
+        let flux: Jansky = ABMagnitude::new(23.9).into();
+        assert!((flux.0 / 1e-6 - 1.).abs() < 1e-3);
+        let m: ABMagnitude = flux.into();
+        assert!((m.0 - 23.9).abs() < 1e-12);
+
+        let offset = DimensionlessFloat::new(0.5);
+        assert!(((m + offset).0 - 24.4).abs() < 1e-12);
+        assert!(((m - offset).0 - 23.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn format_and_parse() {
+        let m = VegaMagnitude::new(21.26);
+        assert_eq!(format!("{:.1}", m), "21.3 mag(Vega)");
+        assert_eq!("21.26 mag(Vega)".parse::<VegaMagnitude>().unwrap(), m);
+        assert_eq!("21.26".parse::<VegaMagnitude>().unwrap(), m);
+        assert_eq!("21.26mag(Vega)".parse::<VegaMagnitude>().unwrap(), m);
+        assert!("21.26 mag(AB)".parse::<VegaMagnitude>().is_err());
+    }
+}
@@ -2,12 +2,29 @@ use std::ops::{Add, Sub};
 
 use crate::{
     constants,
-    units::{macros::floating_point_unit_impl, traits::FloatingPointUnit},
+    units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit},
     Joule,
 };
 
-floating_point_unit_impl! { Kilogram }
-floating_point_unit_impl! { Gram }
+floating_point_unit_impl! { Kilogram, "kg", symbols::MASS }
+floating_point_unit_impl! { Gram, "g", symbols::MASS }
+floating_point_unit_impl! { SolarMass, "Msun", symbols::MASS }
+
+/// Nominal solar mass, $(GM)_\odot^N / G$ using the IAU 2015 Resolution B3
+/// nominal solar mass parameter and the CODATA 2018 gravitational constant.
+pub const SOLAR_MASS_TO_KILOGRAMS: f64 = 1.988409870698051e30;
+
+impl From<SolarMass> for Kilogram {
+    fn from(mass: SolarMass) -> Self {
+        Kilogram::new(mass.0 * SOLAR_MASS_TO_KILOGRAMS)
+    }
+}
+
+impl From<Kilogram> for SolarMass {
+    fn from(mass: Kilogram) -> Self {
+        SolarMass::new(mass.0 / SOLAR_MASS_TO_KILOGRAMS)
+    }
+}
 
 impl From<Joule> for Kilogram {
     fn from(energy: Joule) -> Self {
@@ -38,4 +55,14 @@ mod tests {
         assert!(equivalent_energy > Joule::new(8.9e16));
         assert!(equivalent_energy < Joule::new(9.05e16));
     }
+
+    #[test]
+    fn solar_mass() {
+        let sun: Kilogram = SolarMass::one().into();
+        assert!(sun > Kilogram::new(1.988e30));
+        assert!(sun < Kilogram::new(1.989e30));
+
+        let back: SolarMass = sun.into();
+        assert!((back.0 - 1.).abs() < 1e-12);
+    }
 }
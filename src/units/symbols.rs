@@ -0,0 +1,325 @@
+//! Unit symbols accepted when parsing quantities from strings.
+//!
+//! Each table lists the symbols of a physical dimension together with
+//! the size of that unit in an arbitrary base unit of the dimension.
+//! Whitespace is ignored when matching symbols.
+use anyhow::anyhow;
+
+use crate::units::{
+    energy::ELECTRONVOLT_TO_JOULES,
+    length::{
+        ANGSTROM_TO_METER, KILOMETER_TO_METER, MICROMETER_TO_METER, MPC_TO_KILOMETERS,
+        MPC_TO_METERS, NANOMETER_TO_METER,
+    },
+    luminosity::SOLAR_LUMINOSITY_TO_WATTS,
+    mass::SOLAR_MASS_TO_KILOGRAMS,
+    time::{SECONDS_PER_GYR, SECONDS_PER_YR},
+};
+
+pub(crate) type UnitTable = &'static [(&'static str, f64)];
+
+/// Base unit: meter.
+pub(crate) const LENGTH: UnitTable = &[
+    ("A", ANGSTROM_TO_METER),
+    ("Å", ANGSTROM_TO_METER),
+    ("nm", NANOMETER_TO_METER),
+    ("um", MICROMETER_TO_METER),
+    ("μm", MICROMETER_TO_METER),
+    ("m", 1.),
+    ("km", KILOMETER_TO_METER),
+    ("pc", MPC_TO_METERS * 1e-6),
+    ("kpc", MPC_TO_METERS * 1e-3),
+    ("Mpc", MPC_TO_METERS),
+    ("Gpc", MPC_TO_METERS * 1e3),
+];
+
+/// Base unit: Mpc^3.
+pub(crate) const VOLUME: UnitTable = &[
+    ("kpc^3", 1e-9),
+    ("kpc3", 1e-9),
+    ("Mpc^3", 1.),
+    ("Mpc3", 1.),
+    ("Gpc^3", 1e9),
+    ("Gpc3", 1e9),
+];
+
+/// Base unit: second.
+pub(crate) const TIME: UnitTable = &[
+    ("s", 1.),
+    ("yr", SECONDS_PER_YR),
+    ("Myr", SECONDS_PER_GYR * 1e-3),
+    ("Gyr", SECONDS_PER_GYR),
+];
+
+/// Base unit: km/s/Mpc.
+pub(crate) const HUBBLE_PARAMETER: UnitTable = &[
+    ("km/s/Mpc", 1.),
+    ("kms^-1Mpc^-1", 1.),
+    ("km/(Mpcs)", 1.),
+    ("km/(sMpc)", 1.),
+    ("1/s", MPC_TO_KILOMETERS),
+    ("s^-1", MPC_TO_KILOMETERS),
+];
+
+/// Base unit: hertz.
+pub(crate) const FREQUENCY: UnitTable = &[
+    ("Hz", 1.),
+    ("kHz", 1e3),
+    ("MHz", 1e6),
+    ("GHz", 1e9),
+    ("THz", 1e12),
+];
+
+/// Base unit: kelvin.
+pub(crate) const TEMPERATURE: UnitTable = &[("K", 1.)];
+
+/// Base unit: kilogram.
+pub(crate) const MASS: UnitTable = &[
+    ("g", 1e-3),
+    ("kg", 1.),
+    ("Msun", SOLAR_MASS_TO_KILOGRAMS),
+    ("M_sun", SOLAR_MASS_TO_KILOGRAMS),
+];
+
+/// Base unit: joule.
+pub(crate) const ENERGY: UnitTable = &[
+    ("J", 1.),
+    ("erg", 1e-7),
+    ("eV", ELECTRONVOLT_TO_JOULES),
+    ("keV", ELECTRONVOLT_TO_JOULES * 1e3),
+    ("MeV", ELECTRONVOLT_TO_JOULES * 1e6),
+    ("GeV", ELECTRONVOLT_TO_JOULES * 1e9),
+];
+
+/// Base unit: radian.
+pub(crate) const ANGLE: UnitTable = &[
+    ("rad", 1.),
+    ("deg", std::f64::consts::PI / 180.),
+    ("arcmin", std::f64::consts::PI / (180. * 60.)),
+    ("arcsec", std::f64::consts::PI / (180. * 3600.)),
+];
+
+/// Base unit: steradian.
+pub(crate) const SOLID_ANGLE: UnitTable = &[
+    ("sr", 1.),
+    (
+        "deg^2",
+        (std::f64::consts::PI / 180.) * (std::f64::consts::PI / 180.),
+    ),
+    (
+        "deg2",
+        (std::f64::consts::PI / 180.) * (std::f64::consts::PI / 180.),
+    ),
+];
+
+/// Base unit: kg/m^3.
+pub(crate) const DENSITY: UnitTable = &[
+    ("kg/m^3", 1.),
+    ("g/cm^3", 1e3),
+    (
+        "Msun/Mpc^3",
+        SOLAR_MASS_TO_KILOGRAMS / (MPC_TO_METERS * MPC_TO_METERS * MPC_TO_METERS),
+    ),
+];
+
+/// Base unit: watt.
+pub(crate) const LUMINOSITY: UnitTable = &[
+    ("W", 1.),
+    ("erg/s", 1e-7),
+    ("Lsun", SOLAR_LUMINOSITY_TO_WATTS),
+    ("L_sun", SOLAR_LUMINOSITY_TO_WATTS),
+];
+
+/// Base unit: W/Hz.
+pub(crate) const SPECTRAL_LUMINOSITY_PER_HZ: UnitTable = &[("W/Hz", 1.), ("erg/s/Hz", 1e-7)];
+
+/// Base unit: erg/s/A.
+pub(crate) const SPECTRAL_LUMINOSITY_PER_ANGSTROM: UnitTable = &[("erg/s/A", 1.), ("erg/s/Å", 1.)];
+
+/// Base unit: W/m^2.
+pub(crate) const FLUX: UnitTable = &[("W/m^2", 1.), ("erg/s/cm^2", 1e-3)];
+
+/// Base unit: W/m^2/Hz.
+pub(crate) const SPECTRAL_FLUX_PER_HZ: UnitTable = &[
+    ("W/m^2/Hz", 1.),
+    ("erg/s/cm^2/Hz", 1e-3),
+    ("Jy", 1e-26),
+    ("mJy", 1e-29),
+    ("uJy", 1e-32),
+    ("μJy", 1e-32),
+];
+
+/// Base unit: erg/s/cm^2/A.
+pub(crate) const SPECTRAL_FLUX_PER_ANGSTROM: UnitTable =
+    &[("erg/s/cm^2/A", 1.), ("erg/s/cm^2/Å", 1.)];
+
+/// Base unit: h^-1 Mpc.
+pub(crate) const H_INV_LENGTH: UnitTable = &[
+    ("Mpc/h", 1.),
+    ("h^-1Mpc", 1.),
+    ("kpc/h", 1e-3),
+    ("h^-1kpc", 1e-3),
+];
+
+/// Base unit: h^-3 Mpc^3.
+pub(crate) const H_INV_VOLUME: UnitTable = &[("(Mpc/h)^3", 1.), ("h^-3Mpc^3", 1.)];
+
+/// Base unit: h^-1 Msun.
+pub(crate) const H_INV_MASS: UnitTable = &[("Msun/h", 1.), ("h^-1Msun", 1.)];
+
+/// Base unit: h^2 Msun/Mpc^3.
+pub(crate) const H2_DENSITY: UnitTable = &[("h^2Msun/Mpc^3", 1.)];
+
+/// Parse a quantity such as `"3.2 Gpc"` into the unit `symbol` from `table`.
+///
+/// A bare number is interpreted as already being in the unit `symbol`.
+pub(crate) fn parse_quantity(
+    s: &str,
+    symbol: &str,
+    table: UnitTable,
+) -> Result<f64, anyhow::Error> {
+    let (number, unit) = split_quantity(s);
+    let value = parse_number(number)?;
+
+    let unit: String = unit.chars().filter(|c| !c.is_whitespace()).collect();
+    if unit.is_empty() {
+        return Ok(value);
+    }
+
+    let scale = |target: &str| {
+        table
+            .iter()
+            .find(|(candidate, _)| *candidate == target)
+            .map(|(_, scale)| *scale)
+    };
+    let from = scale(&unit).ok_or_else(|| anyhow!("unknown unit '{}' for {}", unit, symbol))?;
+    let to = scale(&symbol.replace(' ', ""))
+        .ok_or_else(|| anyhow!("unit '{}' missing from its symbol table", symbol))?;
+    if from == to {
+        // Avoid rounding when no conversion is needed
+        return Ok(value);
+    }
+    Ok(value * from / to)
+}
+
+/// Split a quantity such as `"3.2 Gpc"` or `"3.2Gpc"` into its number and unit.
+///
+/// The number is the longest decimal prefix, with an optional sign and
+/// exponent. Anything else, such as `"inf"`, is split at the first whitespace.
+pub(crate) fn split_quantity(s: &str) -> (&str, &str) {
+    let s = s.trim();
+    let bytes = s.as_bytes();
+    let digits_from = |mut i: usize| {
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        i
+    };
+
+    let mut end = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    let integer_end = digits_from(end);
+    let mut has_digits = integer_end > end;
+    end = integer_end;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction_end = digits_from(end + 1);
+        has_digits |= fraction_end > end + 1;
+        end = fraction_end;
+    }
+    if !has_digits {
+        return match s.split_once(char::is_whitespace) {
+            Some((number, unit)) => (number, unit),
+            None => (s, ""),
+        };
+    }
+    // Only an exponent followed by digits, so that e.g. "3eV" is 3 eV
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        let exponent_end = digits_from(end + 1 + sign);
+        if exponent_end > end + 1 + sign {
+            end = exponent_end;
+        }
+    }
+    (&s[..end], &s[end..])
+}
+
+/// Parse a number without units.
+pub(crate) fn parse_number(s: &str) -> Result<f64, anyhow::Error> {
+    s.trim()
+        .parse::<f64>()
+        .map_err(|e| anyhow!("could not parse '{}' as a number: {}", s.trim(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        FloatingPointUnit, Gyr, Hertz, Kelvin, KmPerSecPerMpc, Micrometer, Mpc, Redshift, Seconds,
+    };
+
+    #[test]
+    fn parse_with_conversion() {
+        assert!((parse_quantity("3.2 Gpc", "Mpc", LENGTH).unwrap() - 3200.).abs() < 1e-9);
+        assert_eq!(parse_quantity("3.2", "Mpc", LENGTH).unwrap(), 3.2);
+        assert_eq!(
+            parse_quantity("  69.6   km / s / Mpc ", "km/s/Mpc", HUBBLE_PARAMETER).unwrap(),
+            69.6
+        );
+        assert!(parse_quantity("3.2 Gyr", "Mpc", LENGTH).is_err());
+        assert!(parse_quantity("three Mpc", "Mpc", LENGTH).is_err());
+
+        // Without a space between the number and the unit
+        assert!((parse_quantity("3.2Gpc", "Mpc", LENGTH).unwrap() - 3200.).abs() < 1e-9);
+        assert_eq!(
+            parse_quantity("-1.5e-3Mpc", "Mpc", LENGTH).unwrap(),
+            -1.5e-3
+        );
+        assert!(
+            (parse_quantity("3eV", "J", ENERGY).unwrap() / ELECTRONVOLT_TO_JOULES - 3.).abs()
+                < 1e-12
+        );
+        assert_eq!(
+            parse_quantity("70km/s/Mpc", "km/s/Mpc", HUBBLE_PARAMETER).unwrap(),
+            70.
+        );
+        assert!(parse_quantity("3.2.1 Mpc", "Mpc", LENGTH).is_err());
+        assert!(parse_quantity("inf Mpc", "Mpc", LENGTH)
+            .unwrap()
+            .is_infinite());
+    }
+
+    #[test]
+    fn parse_units() {
+        let H_0: KmPerSecPerMpc = "69.6 km/s/Mpc".parse().unwrap();
+        assert_eq!(H_0, KmPerSecPerMpc::new(69.6));
+        let d: Mpc = "3.2 Gpc".parse().unwrap();
+        assert!((d.0 - 3200.).abs() < 1e-9);
+        let t: Gyr = "11.6 Gyr".parse().unwrap();
+        assert_eq!(t, Gyr::new(11.6));
+        let t: Seconds = "1 Gyr".parse().unwrap();
+        assert_eq!(t, Seconds::new(SECONDS_PER_GYR));
+        let nu: Hertz = "1.4 GHz".parse().unwrap();
+        assert!((nu.0 - 1.4e9).abs() < 1e-3);
+        let lambda: Micrometer = "656.46 nm".parse().unwrap();
+        assert!((lambda.0 - 0.65646).abs() < 1e-12);
+        let T: Kelvin = "2.7255 K".parse().unwrap();
+        assert_eq!(T, Kelvin::new(2.7255));
+        assert_eq!("2.7255K".parse::<Kelvin>().unwrap(), T);
+        let z: Redshift = "1100".parse().unwrap();
+        assert_eq!(z, Redshift::new(1100.));
+        assert!("2 Mpc".parse::<Redshift>().is_err());
+    }
+
+    #[test]
+    fn format_units() {
+        assert_eq!(Mpc::new(3200.).to_string(), "3200 Mpc");
+        assert_eq!(
+            format!("{:.2}", KmPerSecPerMpc::new(69.6)),
+            "69.60 km/s/Mpc"
+        );
+        assert_eq!(format!("{:.1}", Redshift::new(2.)), "2.0");
+
+        // Formatted values parse back to the same quantity
+        let t = Gyr::new(13.787);
+        assert_eq!(t.to_string().parse::<Gyr>().unwrap(), t);
+    }
+}
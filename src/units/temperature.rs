@@ -3,9 +3,9 @@ use std::{
     ops::{Add, Sub},
 };
 
-use crate::units::{macros::floating_point_unit_impl, traits::FloatingPointUnit};
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
 
-floating_point_unit_impl! { Kelvin }
+floating_point_unit_impl! { Kelvin, "K", symbols::TEMPERATURE }
 
 impl Default for Kelvin {
     fn default() -> Self {
@@ -1,9 +1,9 @@
 use std::ops::{Add, Sub};
 
-use crate::units::{macros::floating_point_unit_impl, traits::FloatingPointUnit};
+use crate::units::{macros::floating_point_unit_impl, symbols, traits::FloatingPointUnit};
 
-floating_point_unit_impl! { Gyr }
-floating_point_unit_impl! { Seconds }
+floating_point_unit_impl! { Gyr, "Gyr", symbols::TIME }
+floating_point_unit_impl! { Seconds, "s", symbols::TIME }
 
 pub const SECONDS_PER_YR: f64 = 3.154e+7;
 pub const SECONDS_PER_GYR: f64 = 3.154e+16;
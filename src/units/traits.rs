@@ -5,7 +5,7 @@ pub trait FloatingPointUnit {
     /// Get the inner unit.
     fn inner(&self) -> f64;
 
-    /// Default implementations
+    // Default implementations
 
     /// Get the zero value for this unit.
     fn zero() -> Self
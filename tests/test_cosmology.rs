@@ -1,6 +1,7 @@
 use cosmocalc::{
     cosmology::{FLRWCosmology, OmegaFactors},
-    DimensionlessPositiveFloat, FloatingPointUnit, Gyr, Mpc, Redshift,
+    Background, DimensionlessPositiveFloat, FloatingPointUnit, Gyr, H2MsunPerMpc3, HInvMpc, Mpc,
+    MsunPerMpc3, Redshift,
 };
 
 #[test]
@@ -17,8 +18,8 @@ fn hubble_distance_and_time() {
     );
 
     // Should be around 3000 h^-1 Mpc
-    assert!(cosmology.hubble_distance_little_h() > 2950.0);
-    assert!(cosmology.hubble_distance_little_h() < 3000.0);
+    assert!(cosmology.hubble_distance_little_h() > HInvMpc::new(2950.0));
+    assert!(cosmology.hubble_distance_little_h() < HInvMpc::new(3000.0));
 
     // For H_0 = 70, should be 4285.7 Mpc
     assert!(cosmology.hubble_distance() > Mpc::new(4000.0));
@@ -26,11 +27,20 @@ fn hubble_distance_and_time() {
 
     // D_H in units of h^{-1} Mpc should be equal to D_H in units of Mpc
     assert!(
-        cosmology.hubble_distance_little_h()
+        cosmology.hubble_distance_little_h().0
             - (cosmology.hubble_distance().0 * cosmology.little_h().0)
             < 0.01
     );
 
+    // Removing the factor of h recovers D_H in Mpc
+    assert!(
+        (cosmology.remove_little_h(cosmology.hubble_distance_little_h())
+            - cosmology.hubble_distance())
+        .0
+        .abs()
+            < 0.01
+    );
+
     // t_H should be ~3e17 h^-1 seconds so t_H = 4e17 if h=0.70
     assert!(cosmology.hubble_time().0 > 4.4e17);
     assert!(cosmology.hubble_time().0 < 4.5e17);
@@ -43,6 +53,19 @@ fn densities() {
 
     assert!(cosmology.critical_density(Redshift::zero()).0 > 8.7e-27);
     assert!(cosmology.critical_density(Redshift::zero()).0 < 9.5e-27);
+
+    // rho_crit,0 = 2.775e11 h^2 Msun/Mpc^3
+    let rho_crit_h = cosmology.critical_density_little_h(Redshift::zero());
+    assert!(rho_crit_h > H2MsunPerMpc3::new(2.77e11));
+    assert!(rho_crit_h < H2MsunPerMpc3::new(2.78e11));
+    let rho_crit: MsunPerMpc3 = cosmology.critical_density(Redshift::zero()).into();
+    assert!((rho_crit.0 / rho_crit_h.0 - 0.49).abs() < 1e-9);
+
+    // Mean matter density scales as (1+z)^3
+    let rho_m0 = cosmology.mean_matter_density_little_h(Redshift::zero());
+    assert!((rho_m0.0 / rho_crit_h.0 - 0.27).abs() < 1e-9);
+    let rho_m1 = cosmology.mean_matter_density(Redshift::new(1.0));
+    assert!((rho_m1.0 / cosmology.mean_matter_density(Redshift::zero()).0 - 8.).abs() < 1e-9);
 }
 
 #[test]
@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+
+use cosmocalc::{
+    cosmology::{FLRWCosmology, OmegaFactors},
+    units::PositiveFloat,
+    ABMagnitude, Distances, FloatingPointUnit, Mpc, Redshift,
+};
+
+fn planck_like() -> FLRWCosmology {
+    let omegas = OmegaFactors::new(0.3089, 0.6911, 0.0486).unwrap();
+    FLRWCosmology::new(
+        Some("planck-like".to_string()),
+        None,
+        67.74,
+        omegas,
+        Some(2.7255),
+        None,
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn cosmology_round_trip() {
+    let cosmology = planck_like();
+    let json = serde_json::to_string(&cosmology).unwrap();
+    let loaded: FLRWCosmology = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded, cosmology);
+    let z = Redshift::new(2.0);
+    assert_eq!(
+        loaded.luminosity_distance(z),
+        cosmology.luminosity_distance(z)
+    );
+}
+
+#[test]
+fn derived_fields_are_recomputed() {
+    let cosmology = planck_like();
+    let mut value = serde_json::to_value(&cosmology).unwrap();
+    assert!(value.get("omega_k0").is_none());
+
+    // Derived fields in the input are ignored rather than trusted
+    value["omega_k0"] = serde_json::json!(0.5);
+    let loaded: FLRWCosmology = serde_json::from_value(value).unwrap();
+    assert_eq!(loaded.omega_k0, cosmology.omega_k0);
+}
+
+#[test]
+fn invalid_parameters_are_rejected() {
+    let mut value = serde_json::to_value(planck_like()).unwrap();
+    value["omega"]["Omega_b0"] = serde_json::json!(0.5);
+    assert!(serde_json::from_value::<FLRWCosmology>(value).is_err());
+
+    assert!(serde_json::from_str::<PositiveFloat>("-1.0").is_err());
+}
+
+#[test]
+fn units_are_bare_numbers() {
+    assert_eq!(serde_json::to_string(&Mpc::new(3.5)).unwrap(), "3.5");
+    let z: Redshift = serde_json::from_str("1100.0").unwrap();
+    assert_eq!(z, Redshift::new(1100.));
+
+    let m: ABMagnitude = serde_json::from_str("23.9").unwrap();
+    assert_eq!(m, ABMagnitude::new(23.9));
+    assert_eq!(serde_json::to_string(&m).unwrap(), "23.9");
+}